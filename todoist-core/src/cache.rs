@@ -0,0 +1,449 @@
+//! Caching of resources, so they can be read without a sync.
+//!
+//! Resources are stored as JSON under keys like `todoist:item:42`, in any
+//! [`CacheBackend`]: Redis with the `cache` feature, or [`MemoryCache`] where
+//! Redis is not available.
+//!
+//! [`Cache::apply`] keeps the cache in step with incremental syncs, storing the
+//! sync token under `todoist:sync_token`.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+#[cfg(feature = "cache")]
+use redis::aio::MultiplexedConnection;
+#[cfg(feature = "cache")]
+use redis::{AsyncCommands, RedisError};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::client::SyncResponse;
+use crate::types::items::Item;
+use crate::types::projects::Project;
+
+#[derive(Debug)]
+pub enum CacheError {
+    /// Redis could not be reached or refused the command.
+    #[cfg(feature = "cache")]
+    Redis(RedisError),
+    /// A cached value could not be (de)serialized.
+    Json(serde_json::Error),
+    /// Only resources with an id can be cached, as the id is part of their
+    /// key.
+    NotSaved,
+}
+
+impl fmt::Display for CacheError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(feature = "cache")]
+            CacheError::Redis(err) => write!(f, "Redis request failed: {}", err),
+            CacheError::Json(err) => write!(f, "Invalid cached value: {}", err),
+            CacheError::NotSaved => write!(f, "Cannot cache a resource without an id"),
+        }
+    }
+}
+
+impl Error for CacheError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            #[cfg(feature = "cache")]
+            CacheError::Redis(err) => Some(err),
+            CacheError::Json(err) => Some(err),
+            CacheError::NotSaved => None,
+        }
+    }
+}
+
+#[cfg(feature = "cache")]
+impl From<RedisError> for CacheError {
+    fn from(err: RedisError) -> Self {
+        CacheError::Redis(err)
+    }
+}
+
+impl From<serde_json::Error> for CacheError {
+    fn from(err: serde_json::Error) -> Self {
+        CacheError::Json(err)
+    }
+}
+
+/// Storage of raw values by key, which [`Cache`] keeps the resources in.
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    /// The value stored under `key`, `None` if there is none.
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, CacheError>;
+
+    /// Stores `value` under `key`, replacing any previous value. With a
+    /// `ttl`, the value expires after that long.
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>)
+        -> Result<(), CacheError>;
+
+    /// Removes the value stored under `key`, if any.
+    async fn del(&self, key: &str) -> Result<(), CacheError>;
+}
+
+#[cfg(feature = "cache")]
+#[async_trait]
+impl CacheBackend for MultiplexedConnection {
+    // The connection is multiplexed, so clones share the same socket.
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, CacheError> {
+        Ok(AsyncCommands::get(&mut self.clone(), key).await?)
+    }
+
+    async fn set(
+        &self,
+        key: &str,
+        value: Vec<u8>,
+        ttl: Option<Duration>,
+    ) -> Result<(), CacheError> {
+        let mut connection = self.clone();
+        match ttl {
+            // SETEX takes whole seconds, and rejects 0.
+            Some(ttl) => {
+                let seconds = ttl.as_secs() + u64::from(ttl.subsec_nanos() > 0);
+                AsyncCommands::set_ex::<_, _, ()>(&mut connection, key, value, seconds.max(1))
+                    .await?
+            }
+            None => AsyncCommands::set::<_, _, ()>(&mut connection, key, value).await?,
+        }
+        Ok(())
+    }
+
+    async fn del(&self, key: &str) -> Result<(), CacheError> {
+        AsyncCommands::del::<_, ()>(&mut self.clone(), key).await?;
+        Ok(())
+    }
+}
+
+/// A backend keeping the values in memory, for tests and for platforms without
+/// Redis. Nothing outlives the process.
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+/// A value of a [`MemoryCache`] and when it expires.
+#[derive(Debug)]
+struct Entry {
+    value: Vec<u8>,
+    expiry: Option<Instant>,
+}
+
+impl MemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CacheBackend for MemoryCache {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, CacheError> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some(Entry {
+                expiry: Some(expiry),
+                ..
+            }) if *expiry <= Instant::now() => {
+                entries.remove(key);
+                Ok(None)
+            }
+            entry => Ok(entry.map(|entry| entry.value.clone())),
+        }
+    }
+
+    async fn set(
+        &self,
+        key: &str,
+        value: Vec<u8>,
+        ttl: Option<Duration>,
+    ) -> Result<(), CacheError> {
+        let expiry = ttl.map(|ttl| Instant::now() + ttl);
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), Entry { value, expiry });
+        Ok(())
+    }
+
+    async fn del(&self, key: &str) -> Result<(), CacheError> {
+        self.entries.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+/// Shares one backend between several caches, e.g. with different key
+/// prefixes.
+#[async_trait]
+impl<B: CacheBackend + ?Sized> CacheBackend for Arc<B> {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, CacheError> {
+        (**self).get(key).await
+    }
+
+    async fn set(
+        &self,
+        key: &str,
+        value: Vec<u8>,
+        ttl: Option<Duration>,
+    ) -> Result<(), CacheError> {
+        (**self).set(key, value, ttl).await
+    }
+
+    async fn del(&self, key: &str) -> Result<(), CacheError> {
+        (**self).del(key).await
+    }
+}
+
+/// How a [`Cache`] stores its entries.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CacheConfig {
+    /// How long entries written without an explicit expiry last. `None`
+    /// keeps them until they are replaced or removed.
+    pub default_ttl: Option<Duration>,
+    /// Put in front of every key, so that several applications can share one
+    /// Redis server.
+    pub key_prefix: String,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            default_ttl: None,
+            key_prefix: String::from("todoist"),
+        }
+    }
+}
+
+/// A cache of resources, stored in the backend `B`.
+pub struct Cache<B> {
+    backend: B,
+    config: CacheConfig,
+}
+
+impl<B: CacheBackend> Cache<B> {
+    pub fn new(backend: B, config: CacheConfig) -> Self {
+        Cache { backend, config }
+    }
+
+    /// The key the resource of the given kind and id is cached under, e.g.
+    /// `todoist:item:42`.
+    fn key(&self, kind: &str, id: u64) -> String {
+        format!("{}:{}:{}", self.config.key_prefix, kind, id)
+    }
+
+    async fn set<T: Serialize + Sync>(
+        &self,
+        kind: &str,
+        id: Option<u64>,
+        value: &T,
+        ttl: Option<Duration>,
+    ) -> Result<(), CacheError> {
+        let key = self.key(kind, id.ok_or(CacheError::NotSaved)?);
+        let json = serde_json::to_vec(value)?;
+        self.backend.set(&key, json, ttl).await
+    }
+
+    async fn get<T: DeserializeOwned>(&self, kind: &str, id: u64) -> Result<Option<T>, CacheError> {
+        match self.backend.get(&self.key(kind, id)).await? {
+            Some(json) => Ok(Some(serde_json::from_slice(&json)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Caches `item`, replacing any previous version of it, for the default
+    /// time to live.
+    pub async fn set_item(&self, item: &Item) -> Result<(), CacheError> {
+        self.set("item", item.id(), item, self.config.default_ttl)
+            .await
+    }
+
+    /// Caches `item` until `ttl` has passed.
+    pub async fn set_item_ex(&self, item: &Item, ttl: Duration) -> Result<(), CacheError> {
+        self.set("item", item.id(), item, Some(ttl)).await
+    }
+
+    /// The cached item with the given id, `None` if it was never cached or
+    /// expired.
+    pub async fn get_item(&self, id: u64) -> Result<Option<Item>, CacheError> {
+        self.get("item", id).await
+    }
+
+    /// Drops the item with the given id from the cache, e.g. once it was
+    /// deleted.
+    pub async fn remove_item(&self, id: u64) -> Result<(), CacheError> {
+        self.backend.del(&self.key("item", id)).await
+    }
+
+    /// Caches `project`, replacing any previous version of it, for the
+    /// default time to live.
+    pub async fn set_project(&self, project: &Project) -> Result<(), CacheError> {
+        self.set("project", project.id(), project, self.config.default_ttl)
+            .await
+    }
+
+    /// Caches `project` until `ttl` has passed.
+    pub async fn set_project_ex(&self, project: &Project, ttl: Duration) -> Result<(), CacheError> {
+        self.set("project", project.id(), project, Some(ttl)).await
+    }
+
+    /// The cached project with the given id, `None` if it was never cached or
+    /// expired.
+    pub async fn get_project(&self, id: u64) -> Result<Option<Project>, CacheError> {
+        self.get("project", id).await
+    }
+
+    /// Drops the project with the given id from the cache.
+    pub async fn remove_project(&self, id: u64) -> Result<(), CacheError> {
+        self.backend.del(&self.key("project", id)).await
+    }
+
+    /// The key the token of the last applied sync is stored under, e.g.
+    /// `todoist:sync_token`.
+    fn sync_token_key(&self) -> String {
+        format!("{}:sync_token", self.config.key_prefix)
+    }
+
+    /// The token of the last sync applied with [`Cache::apply`], to ask the
+    /// API for the changes since. `None` before the first sync.
+    pub async fn sync_token(&self) -> Result<Option<String>, CacheError> {
+        match self.backend.get(&self.sync_token_key()).await? {
+            Some(json) => Ok(Some(serde_json::from_slice(&json)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn set_sync_token(&self, token: &str) -> Result<(), CacheError> {
+        let json = serde_json::to_vec(token)?;
+        self.backend
+            .set(&self.sync_token_key(), json, self.config.default_ttl)
+            .await
+    }
+
+    /// Caches the items and projects of a sync response, drops those it flags
+    /// as deleted, then stores its sync token.
+    ///
+    /// Resources missing from a full sync are not dropped, since the cache
+    /// cannot list its keys. With a `default_ttl`, resources that expired are
+    /// only sent again by a full sync, once the token expired as well.
+    pub async fn apply(&self, response: &SyncResponse) -> Result<(), CacheError> {
+        for item in response.items() {
+            match item.id() {
+                Some(id) if item.is_deleted() => self.remove_item(id).await?,
+                Some(_) => self.set_item(item).await?,
+                None => continue,
+            }
+        }
+        for project in response.projects() {
+            match project.id() {
+                Some(id) if project.is_deleted() => self.remove_project(id).await?,
+                Some(_) => self.set_project(project).await?,
+                None => continue,
+            }
+        }
+        self.set_sync_token(response.sync_token()).await
+    }
+}
+
+#[cfg(feature = "cache")]
+impl Cache<MultiplexedConnection> {
+    /// Connects to the Redis server at `url`, e.g. `"redis://127.0.0.1/"`.
+    pub async fn connect(url: &str, config: CacheConfig) -> Result<Self, CacheError> {
+        let client = redis::Client::open(url)?;
+        let connection = client.get_multiplexed_async_connection().await?;
+        Ok(Cache::new(connection, config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn cache_key_prefix_test() {
+        let backend = Arc::new(MemoryCache::new());
+        let config = |prefix: &str| CacheConfig {
+            key_prefix: prefix.to_string(),
+            ..CacheConfig::default()
+        };
+        let cli = Cache::new(backend.clone(), config("cli"));
+        let web = Cache::new(backend.clone(), config("web"));
+        assert_eq!(cli.key("item", 42), "cli:item:42");
+
+        let item = Item::builder()
+            .id(42)
+            .user_id(1)
+            .project_id(2)
+            .content("Buy milk")
+            .build()
+            .unwrap();
+        cli.set_item(&item).await.unwrap();
+
+        assert_eq!(cli.get_item(42).await.unwrap(), Some(item));
+        assert_eq!(web.get_item(42).await.unwrap(), None);
+        assert!(backend.get("cli:item:42").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn cache_ttl_test() {
+        let backend = Arc::new(MemoryCache::new());
+        let cache = Cache::new(backend.clone(), CacheConfig::default());
+        let project = Project::builder().id(2).name("Work").build().unwrap();
+        let expiry = |key: &str| backend.entries.lock().unwrap()[key].expiry;
+
+        cache.set_project(&project).await.unwrap();
+        assert_eq!(expiry("todoist:project:2"), None);
+        assert_eq!(cache.get_project(2).await.unwrap(), Some(project));
+
+        let project = Project::builder().id(3).name("Home").build().unwrap();
+        cache
+            .set_project_ex(&project, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(expiry("todoist:project:3").is_some());
+        cache
+            .set_project_ex(&project, Duration::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(cache.get_project(3).await.unwrap(), None);
+    }
+
+    /// Round trips an item through `cache`. Uses ids unlikely to be taken on
+    /// a shared Redis server.
+    async fn check_item_cache<B: CacheBackend>(cache: Cache<B>) {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("Buy milk");
+
+        assert!(matches!(
+            cache.set_item(&builder.build().unwrap()).await,
+            Err(CacheError::NotSaved)
+        ));
+
+        let item = builder.id(u64::MAX - 1).build().unwrap();
+        cache.set_item(&item).await.unwrap();
+        assert_eq!(cache.get_item(u64::MAX - 1).await.unwrap(), Some(item));
+        assert_eq!(cache.get_item(u64::MAX - 2).await.unwrap(), None);
+
+        cache.remove_item(u64::MAX - 1).await.unwrap();
+        assert_eq!(cache.get_item(u64::MAX - 1).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn memory_cache_item_test() {
+        check_item_cache(Cache::new(MemoryCache::new(), CacheConfig::default())).await;
+    }
+
+    #[cfg(feature = "redis-tests")]
+    #[tokio::test]
+    async fn redis_cache_item_test() {
+        let url = std::env::var("REDIS_URL").unwrap_or_else(|_| String::from("redis://127.0.0.1/"));
+        let config = CacheConfig {
+            key_prefix: String::from("todoist-core-test"),
+            ..CacheConfig::default()
+        };
+        check_item_cache(Cache::connect(&url, config).await.unwrap()).await;
+    }
+}
@@ -0,0 +1,367 @@
+//! Commands changing resources through the `sync` endpoint.
+use std::error::Error;
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::client::TempId;
+use crate::types::error::BuildError;
+use crate::types::items::{Item, ItemBuilder};
+use crate::types::projects::ProjectBuilder;
+use crate::types::user::UserSetting;
+
+/// A single change sent to the `sync` endpoint. Every command carries a unique
+/// `uuid`, which the response's `sync_status` reports the command's outcome
+/// under.
+#[derive(Serialize, Debug, PartialEq, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Command {
+    ItemAdd {
+        uuid: String,
+        temp_id: TempId,
+        args: Value,
+    },
+    ItemUpdate {
+        uuid: String,
+        args: Value,
+    },
+    ItemMove {
+        uuid: String,
+        args: Value,
+    },
+    ItemDelete {
+        uuid: String,
+        args: Value,
+    },
+    ItemComplete {
+        uuid: String,
+        args: Value,
+    },
+    ProjectAdd {
+        uuid: String,
+        temp_id: TempId,
+        args: Value,
+    },
+    ProjectDelete {
+        uuid: String,
+        args: Value,
+    },
+    UserUpdate {
+        uuid: String,
+        args: Value,
+    },
+}
+
+/// Where an `item_move` command moves an item to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MoveTarget {
+    Project(u64),
+    Section(u64),
+    Parent(u64),
+}
+
+impl Command {
+    /// An `item_add` command creating an item with the fields set on
+    /// `builder`, see [`ItemBuilder::add_args`]. The item goes by a new
+    /// temporary id until the server assigns the real one.
+    pub fn item_add(builder: &ItemBuilder) -> Command {
+        Command::ItemAdd {
+            uuid: Uuid::new_v4().to_string(),
+            temp_id: Uuid::new_v4().to_string(),
+            args: builder.add_args(),
+        }
+    }
+
+    /// An `item_add` command creating a copy of `item` under a new id.
+    pub fn item_add_from(item: &Item) -> Command {
+        Command::item_add(&item.as_builder())
+    }
+
+    /// An `item_update` command setting the fields set on `builder`, see
+    /// [`ItemBuilder::update_args`].
+    pub fn item_update(builder: &ItemBuilder) -> Result<Command, BuildError> {
+        Ok(Command::ItemUpdate {
+            uuid: Uuid::new_v4().to_string(),
+            args: builder.update_args()?,
+        })
+    }
+
+    pub fn item_move(id: u64, target: MoveTarget) -> Command {
+        let args = match target {
+            MoveTarget::Project(project_id) => json!({ "id": id, "project_id": project_id }),
+            MoveTarget::Section(section_id) => json!({ "id": id, "section_id": section_id }),
+            MoveTarget::Parent(parent_id) => json!({ "id": id, "parent_id": parent_id }),
+        };
+        Command::ItemMove {
+            uuid: Uuid::new_v4().to_string(),
+            args,
+        }
+    }
+
+    /// An `item_delete` command deleting the item and its subtasks.
+    pub fn item_delete(id: u64) -> Command {
+        Command::ItemDelete {
+            uuid: Uuid::new_v4().to_string(),
+            args: json!({ "id": id }),
+        }
+    }
+
+    /// An `item_complete` command completing the item and its subtasks.
+    pub fn item_complete(id: u64) -> Command {
+        Command::ItemComplete {
+            uuid: Uuid::new_v4().to_string(),
+            args: json!({ "id": id }),
+        }
+    }
+
+    /// A `project_add` command creating a project with the fields set on
+    /// `builder`, see [`ProjectBuilder::add_args`]. The project goes by a new
+    /// temporary id until the server assigns the real one.
+    pub fn project_add(builder: &ProjectBuilder) -> Command {
+        Command::ProjectAdd {
+            uuid: Uuid::new_v4().to_string(),
+            temp_id: Uuid::new_v4().to_string(),
+            args: builder.add_args(),
+        }
+    }
+
+    /// A `project_delete` command deleting the project and everything in it.
+    pub fn project_delete(id: u64) -> Command {
+        Command::ProjectDelete {
+            uuid: Uuid::new_v4().to_string(),
+            args: json!({ "id": id }),
+        }
+    }
+
+    pub fn user_update(setting: &UserSetting) -> Command {
+        Command::UserUpdate {
+            uuid: Uuid::new_v4().to_string(),
+            args: setting.args(),
+        }
+    }
+
+    pub fn uuid(&self) -> &str {
+        match self {
+            Command::ItemAdd { uuid, .. }
+            | Command::ItemUpdate { uuid, .. }
+            | Command::ItemMove { uuid, .. }
+            | Command::ItemDelete { uuid, .. }
+            | Command::ItemComplete { uuid, .. }
+            | Command::ProjectAdd { uuid, .. }
+            | Command::ProjectDelete { uuid, .. }
+            | Command::UserUpdate { uuid, .. } => uuid,
+        }
+    }
+
+    /// The temporary id of the resource added by an `item_add` or
+    /// `project_add` command, to find its real id in the response's
+    /// `temp_id_mapping`.
+    pub fn temp_id(&self) -> Option<&str> {
+        match self {
+            Command::ItemAdd { temp_id, .. } | Command::ProjectAdd { temp_id, .. } => Some(temp_id),
+            _ => None,
+        }
+    }
+
+    /// The problems [`CommandBatch::validate`] finds with this command.
+    fn check(&self) -> Vec<CommandError> {
+        let missing = |field| CommandError::Missing {
+            uuid: self.uuid().to_string(),
+            field,
+        };
+        let mut errors = Vec::new();
+        match self {
+            Command::ItemAdd { args, .. } => {
+                if args["content"].as_str().is_none_or(|c| c.trim().is_empty()) {
+                    errors.push(missing("content"));
+                }
+            }
+            Command::ItemUpdate { args, .. }
+            | Command::ItemDelete { args, .. }
+            | Command::ItemComplete { args, .. }
+            | Command::ProjectDelete { args, .. } => {
+                if !args["id"].is_u64() {
+                    errors.push(missing("id"));
+                }
+            }
+            Command::ItemMove { args, .. } => {
+                if !args["id"].is_u64() {
+                    errors.push(missing("id"));
+                }
+                if ["project_id", "section_id", "parent_id"]
+                    .iter()
+                    .all(|target| !args[target].is_u64())
+                {
+                    errors.push(CommandError::NoTarget {
+                        uuid: self.uuid().to_string(),
+                    });
+                }
+            }
+            Command::ProjectAdd { args, .. } => {
+                if args["name"].as_str().is_none_or(|n| n.trim().is_empty()) {
+                    errors.push(missing("name"));
+                }
+            }
+            Command::UserUpdate { .. } => {}
+        }
+        errors
+    }
+}
+
+/// A problem with a command found before sending it, see
+/// [`CommandBatch::validate`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CommandError {
+    /// The command with this uuid lacks an argument the API requires.
+    Missing { uuid: String, field: &'static str },
+    /// The `item_move` command with this uuid has nowhere to move the item to.
+    NoTarget { uuid: String },
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CommandError::Missing { uuid, field } => {
+                write!(f, "Command {} is missing {}", uuid, field)
+            }
+            CommandError::NoTarget { uuid } => {
+                write!(f, "Command {} does not say where to move the item", uuid)
+            }
+        }
+    }
+}
+
+impl Error for CommandError {}
+
+/// Commands to send together in one request.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct CommandBatch {
+    commands: Vec<Command>,
+}
+
+impl CommandBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, command: Command) -> &mut Self {
+        self.commands.push(command);
+        self
+    }
+
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// Runs the checks that can be done without the server, so a broken batch
+    /// fails before a round trip. Reports every problem of every command.
+    pub fn validate(&self) -> Result<(), Vec<CommandError>> {
+        let errors: Vec<CommandError> = self.commands.iter().flat_map(Command::check).collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::projects::Project;
+
+    #[test]
+    fn user_update_command_test() {
+        let command = Command::user_update(&UserSetting::StartDay(7));
+        let json = serde_json::to_value(&command).unwrap();
+
+        assert_eq!(json["type"], json!("user_update"));
+        assert_eq!(json["uuid"], json!(command.uuid()));
+        assert_eq!(json["args"], json!({ "start_day": 7 }));
+    }
+
+    #[test]
+    fn item_add_command_test() {
+        let item = Item::builder()
+            .user_id(1)
+            .project_id(2)
+            .content("Buy milk")
+            .set_labels(vec![3])
+            .build()
+            .unwrap();
+        let command = Command::item_add_from(&item);
+        let json = serde_json::to_value(&command).unwrap();
+
+        assert_eq!(json["type"], json!("item_add"));
+        assert_eq!(json["uuid"], json!(command.uuid()));
+        assert_eq!(json["temp_id"], json!(command.temp_id().unwrap()));
+        assert_ne!(command.uuid(), command.temp_id().unwrap());
+        assert_eq!(
+            json["args"],
+            json!({
+                "content": "Buy milk",
+                "description": "",
+                "due": null,
+                "priority": 1,
+                "section_id": null,
+                "labels": [3],
+                "project_id": 2,
+            })
+        );
+    }
+
+    #[test]
+    fn project_commands_test() {
+        let add = Command::project_add(Project::builder().name("Work"));
+        assert_eq!(
+            serde_json::to_value(&add).unwrap()["type"],
+            json!("project_add")
+        );
+        assert_eq!(add.temp_id().map(str::len), Some(36));
+
+        let delete = Command::project_delete(2);
+        let json = serde_json::to_value(&delete).unwrap();
+        assert_eq!(json["type"], json!("project_delete"));
+        assert_eq!(json["args"], json!({ "id": 2 }));
+        assert_eq!(delete.temp_id(), None);
+
+        let mut batch = CommandBatch::new();
+        batch
+            .push(add)
+            .push(Command::project_add(&Project::builder()));
+        assert_eq!(batch.validate().unwrap_err().len(), 1);
+    }
+
+    #[test]
+    fn command_batch_validate_test() {
+        let mut valid = Item::builder();
+        valid.content("Buy milk").project_id(1);
+        let mut batch = CommandBatch::new();
+        batch
+            .push(Command::item_add(&valid))
+            .push(Command::item_move(7, MoveTarget::Section(3)));
+        assert_eq!(batch.validate(), Ok(()));
+
+        let invalid = Command::item_add(Item::builder().project_id(1));
+        let no_target = Command::ItemMove {
+            uuid: String::from("move"),
+            args: json!({ "id": 7 }),
+        };
+        batch.push(invalid.clone()).push(no_target);
+
+        assert_eq!(
+            batch.validate(),
+            Err(vec![
+                CommandError::Missing {
+                    uuid: invalid.uuid().to_string(),
+                    field: "content",
+                },
+                CommandError::NoTarget {
+                    uuid: String::from("move"),
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,374 @@
+//! The HTTP implementation of [`SyncApi`].
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Response, StatusCode};
+
+use crate::cache::{Cache, CacheBackend};
+use crate::client::{ClientError, Command, SyncApi, SyncResponse, BASE_URL};
+
+/// How a [`Client`] retries requests the API answered with `429 Too Many
+/// Requests` or a `5xx` status, which are usually transient.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt; 0 disables retrying.
+    pub max_retries: u32,
+    /// Wait before the first retry, doubled for every further one.
+    pub base_delay: Duration,
+    /// Longest wait between two attempts, also capping `Retry-After`.
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// The wait before retry number `retry`, counting from 0, when the API
+    /// did not send a `Retry-After`.
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.checked_pow(retry).unwrap_or(u32::MAX);
+        self.base_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Whether a request answered with `status` may succeed when sent again.
+fn is_transient(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// The wait the API asked for in the `Retry-After` header, when given in
+/// seconds.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let seconds = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+    seconds.trim().parse().ok().map(Duration::from_secs)
+}
+
+/// Sends requests to the `sync` endpoint of the Todoist servers.
+pub struct Client {
+    http: reqwest::Client,
+    token: String,
+    base_url: String,
+    retry: RetryPolicy,
+}
+
+impl Client {
+    /// Creates a client authenticating with the user's API token.
+    pub fn new(token: &str) -> Self {
+        Self::with_base_url(token, BASE_URL)
+    }
+
+    /// Creates a client sending its requests to `base_url` instead of the
+    /// Todoist servers, e.g. a local mock.
+    pub fn with_base_url(token: &str, base_url: &str) -> Self {
+        Client {
+            http: reqwest::Client::new(),
+            token: token.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Replaces the default [`RetryPolicy`].
+    pub fn retry_policy(&mut self, policy: RetryPolicy) -> &mut Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Reads the given resource types, e.g. `["items", "projects"]` or
+    /// `["all"]`. A `sync_token` of `"*"` asks for a full sync, anything else
+    /// for the changes since that token was issued.
+    pub async fn sync(
+        &self,
+        sync_token: &str,
+        resource_types: &[&str],
+    ) -> Result<SyncResponse, ClientError> {
+        let resource_types = serde_json::to_string(resource_types)?;
+        self.post(&[
+            ("sync_token", sync_token),
+            ("resource_types", &resource_types),
+        ])
+        .await
+    }
+
+    /// Syncs the items and projects that changed since the sync token stored
+    /// in `cache`, or all of them on the first call, and applies them to
+    /// `cache`, see [`Cache::apply`].
+    pub async fn sync_incremental<B: CacheBackend>(
+        &self,
+        cache: &Cache<B>,
+    ) -> Result<SyncResponse, ClientError> {
+        let sync_token = cache.sync_token().await?;
+        let sync_token = sync_token.as_deref().unwrap_or("*");
+        let response = self.sync(sync_token, &["items", "projects"]).await?;
+        cache.apply(&response).await?;
+        Ok(response)
+    }
+
+    /// Sends commands changing resources. The outcome of each command is in
+    /// the response's `sync_status`.
+    ///
+    /// Like syncs, writes are retried on transient errors. The API runs a
+    /// command only once per uuid, so a retry cannot apply it twice.
+    pub async fn write(&self, commands: &[Command]) -> Result<SyncResponse, ClientError> {
+        let commands = serde_json::to_string(commands)?;
+        self.post(&[("commands", &commands)]).await
+    }
+
+    /// Posts `form`, retrying on transient errors as told by the
+    /// [`RetryPolicy`]. Other errors fail right away.
+    async fn post(&self, form: &[(&str, &str)]) -> Result<SyncResponse, ClientError> {
+        let mut retry = 0;
+        let response = loop {
+            let response = self
+                .http
+                .post(format!("{}/sync", self.base_url))
+                .bearer_auth(&self.token)
+                .form(form)
+                .send()
+                .await?;
+            if !is_transient(response.status()) || retry >= self.retry.max_retries {
+                break response;
+            }
+            let delay = retry_after(&response).map_or_else(
+                || self.retry.backoff(retry),
+                |delay| delay.min(self.retry.max_delay),
+            );
+            tokio::time::sleep(delay).await;
+            retry += 1;
+        };
+        let body = response.error_for_status()?.text().await?;
+        Ok(serde_json::from_str(&body)?)
+    }
+}
+
+#[async_trait]
+impl SyncApi for Client {
+    async fn sync(
+        &self,
+        sync_token: &str,
+        resource_types: &[&str],
+    ) -> Result<SyncResponse, ClientError> {
+        Client::sync(self, sync_token, resource_types).await
+    }
+
+    async fn write(&self, commands: &[Command]) -> Result<SyncResponse, ClientError> {
+        Client::write(self, commands).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{CacheConfig, MemoryCache};
+    use wiremock::matchers::{body_string_contains, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn client_sync_test() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/sync"))
+            .and(header("authorization", "Bearer secret"))
+            .and(body_string_contains("sync_token=*"))
+            .and(body_string_contains("resource_types=%5B%22items%22%5D"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"sync_token": "abc", "full_sync": true, "items": [
+                    {"id": 7, "user_id": 1, "project_id": 2, "content": "Call Bob",
+                     "description": "", "due": null, "priority": 1,
+                     "parent_id": null, "child_order": 1, "section_id": null,
+                     "labels": [], "checked": false, "is_deleted": false}
+                ]}"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::with_base_url("secret", &server.uri());
+        let response = client.sync("*", &["items"]).await.unwrap();
+        assert_eq!(response.sync_token(), "abc");
+        assert!(response.full_sync());
+        assert_eq!(response.items()[0].content(), "Call Bob");
+    }
+
+    #[tokio::test]
+    async fn client_sync_error_test() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/sync"))
+            .respond_with(ResponseTemplate::new(403))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/sync"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("{}"))
+            .mount(&server)
+            .await;
+
+        let client = Client::with_base_url("wrong", &server.uri());
+        let forbidden = client.sync("*", &["all"]).await.unwrap_err();
+        assert!(matches!(forbidden, ClientError::Http(_)));
+        let invalid = client.sync("*", &["all"]).await.unwrap_err();
+        assert!(matches!(invalid, ClientError::Json(_)));
+    }
+
+    #[tokio::test]
+    async fn client_sync_incremental_test() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("sync_token=*"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"sync_token": "first", "full_sync": true, "items": [
+                    {"id": 7, "user_id": 1, "project_id": 2, "content": "Call Bob",
+                     "description": "", "due": null, "priority": 1,
+                     "parent_id": null, "child_order": 1, "section_id": null,
+                     "labels": [], "checked": false, "is_deleted": false},
+                    {"id": 8, "user_id": 1, "project_id": 2, "content": "Buy milk",
+                     "description": "", "due": null, "priority": 1,
+                     "parent_id": null, "child_order": 2, "section_id": null,
+                     "labels": [], "checked": false, "is_deleted": false}
+                ]}"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("sync_token=first"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"sync_token": "second", "full_sync": false, "items": [
+                    {"id": 7, "user_id": 1, "project_id": 2, "content": "Call Alice",
+                     "description": "", "due": null, "priority": 1,
+                     "parent_id": null, "child_order": 1, "section_id": null,
+                     "labels": [], "checked": false, "is_deleted": false},
+                    {"id": 8, "user_id": 1, "project_id": 2, "content": "Buy milk",
+                     "description": "", "due": null, "priority": 1,
+                     "parent_id": null, "child_order": 2, "section_id": null,
+                     "labels": [], "checked": false, "is_deleted": true}
+                ]}"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::with_base_url("secret", &server.uri());
+        let cache = Cache::new(MemoryCache::new(), CacheConfig::default());
+
+        assert!(client.sync_incremental(&cache).await.unwrap().full_sync());
+        assert_eq!(cache.sync_token().await.unwrap().as_deref(), Some("first"));
+        assert!(cache.get_item(8).await.unwrap().is_some());
+
+        let response = client.sync_incremental(&cache).await.unwrap();
+        assert!(!response.full_sync());
+        assert_eq!(cache.sync_token().await.unwrap().as_deref(), Some("second"));
+        let item = cache.get_item(7).await.unwrap().unwrap();
+        assert_eq!(item.content(), "Call Alice");
+        assert_eq!(cache.get_item(8).await.unwrap(), None);
+    }
+
+    /// A policy retrying quickly, so the tests do not wait.
+    fn quick_retries(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(10),
+        }
+    }
+
+    #[tokio::test]
+    async fn client_retry_test() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"sync_token": "abc", "full_sync": true}"#),
+            )
+            .mount(&server)
+            .await;
+
+        let mut client = Client::with_base_url("secret", &server.uri());
+        client.retry_policy(quick_retries(3));
+        let response = client.sync("*", &["all"]).await.unwrap();
+        assert_eq!(response.sync_token(), "abc");
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn client_retry_give_up_test() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let mut client = Client::with_base_url("secret", &server.uri());
+        client.retry_policy(quick_retries(2));
+        let error = client.write(&[]).await.unwrap_err();
+        assert!(matches!(error, ClientError::Http(_)));
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+
+        server.reset().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        assert!(client.sync("*", &["all"]).await.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn client_retry_after_test() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"sync_token": "abc", "full_sync": true}"#),
+            )
+            .mount(&server)
+            .await;
+
+        // The backoff alone would wait for a minute.
+        let mut client = Client::with_base_url("secret", &server.uri());
+        client.retry_policy(RetryPolicy {
+            max_retries: 1,
+            base_delay: Duration::from_secs(60),
+            max_delay: Duration::from_secs(60),
+        });
+        let response = tokio::time::timeout(Duration::from_secs(5), client.sync("*", &["all"]))
+            .await
+            .expect("Retry-After is honored");
+        assert_eq!(response.unwrap().sync_token(), "abc");
+    }
+
+    #[test]
+    fn retry_policy_backoff_test() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(4), Duration::from_secs(1));
+        assert_eq!(policy.backoff(40), Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,754 @@
+//! Requests to the Todoist Sync API.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::time::{self, Instant};
+
+use crate::cache::CacheError;
+use crate::store::Store;
+use crate::types::collaborators::{Collaborator, CollaboratorState, CollaboratorStatus};
+use crate::types::filters::Filter;
+use crate::types::items::Item;
+use crate::types::labels::Label;
+use crate::types::notes::Note;
+use crate::types::projects::Project;
+use crate::types::sections::Section;
+use crate::types::user::{User, UserSetting};
+
+mod commands;
+mod http;
+pub mod rest;
+
+pub use commands::{Command, CommandBatch, CommandError, MoveTarget};
+pub use http::{Client, RetryPolicy};
+
+/// Where the Todoist servers serve the Sync API.
+const BASE_URL: &str = "https://api.todoist.com/sync/v8";
+
+/// Time between the syncs of [`TodoistClient::wait_for_item`].
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Client generated id standing in for the id of a resource added by a command
+/// until the server assigns the real one.
+pub type TempId = String;
+
+/// Outcome of a single command sent to the `sync` endpoint.
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum CommandStatus {
+    /// Always the string `"ok"`.
+    Ok(String),
+    Error {
+        error_code: i64,
+        error: String,
+    },
+}
+
+impl CommandStatus {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, CommandStatus::Ok(_))
+    }
+}
+
+/// Outcome of every command of a request, keyed by the command's uuid.
+#[derive(Deserialize, Debug, Default, PartialEq)]
+pub struct SyncStatus(HashMap<String, CommandStatus>);
+
+impl SyncStatus {
+    pub fn get(&self, uuid: &str) -> Option<&CommandStatus> {
+        self.0.get(uuid)
+    }
+
+    /// Whether every command succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.0.values().all(CommandStatus::is_ok)
+    }
+}
+
+/// Real ids of the resources added by the commands of a request, keyed by the
+/// temporary ids the commands used.
+#[derive(Deserialize, Debug, Default, PartialEq)]
+pub struct TempIdMap(HashMap<TempId, u64>);
+
+impl TempIdMap {
+    pub fn get(&self, temp_id: &str) -> Option<u64> {
+        self.0.get(temp_id).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Body of a response from the `sync` endpoint.
+///
+/// Incremental syncs only carry the resources that changed since the sync token
+/// that was sent, so every resource list defaults to empty.
+#[derive(Deserialize, Debug, PartialEq)]
+pub struct SyncResponse {
+    pub(crate) sync_token: String,
+    pub(crate) full_sync: bool,
+    #[serde(default)]
+    pub(crate) items: Vec<Item>,
+    #[serde(default)]
+    pub(crate) projects: Vec<Project>,
+    #[serde(default)]
+    pub(crate) labels: Vec<Label>,
+    #[serde(default)]
+    pub(crate) filters: Vec<Filter>,
+    #[serde(default)]
+    pub(crate) sections: Vec<Section>,
+    #[serde(default)]
+    pub(crate) notes: Vec<Note>,
+    #[serde(default)]
+    pub(crate) collaborators: Vec<Collaborator>,
+    #[serde(default)]
+    pub(crate) collaborator_states: Vec<CollaboratorState>,
+    #[serde(default)]
+    pub(crate) day_orders: HashMap<u64, i32>,
+    #[serde(default)]
+    pub(crate) user: Option<User>,
+    #[serde(default)]
+    pub(crate) temp_id_mapping: TempIdMap,
+    #[serde(default)]
+    pub(crate) sync_status: SyncStatus,
+}
+
+impl SyncResponse {
+    pub fn sync_token(&self) -> &str {
+        &self.sync_token
+    }
+
+    pub fn full_sync(&self) -> bool {
+        self.full_sync
+    }
+
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    pub fn projects(&self) -> &[Project] {
+        &self.projects
+    }
+
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    pub fn filters(&self) -> &[Filter] {
+        &self.filters
+    }
+
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    /// Notes on items and projects alike.
+    pub fn notes(&self) -> &[Note] {
+        &self.notes
+    }
+
+    /// Everyone any shared project is shared with.
+    pub fn collaborators(&self) -> &[Collaborator] {
+        &self.collaborators
+    }
+
+    /// Which projects the collaborators are members of.
+    pub fn collaborator_states(&self) -> &[CollaboratorState] {
+        &self.collaborator_states
+    }
+
+    /// Positions of items in the Today view, keyed by item id.
+    pub fn day_orders(&self) -> &HashMap<u64, i32> {
+        &self.day_orders
+    }
+
+    /// The user, only sent by full syncs and when the user changed.
+    pub fn user(&self) -> Option<&User> {
+        self.user.as_ref()
+    }
+
+    /// Real ids of the resources added by the commands of the request, keyed
+    /// by the temporary ids the commands used.
+    pub fn temp_id_mapping(&self) -> &TempIdMap {
+        &self.temp_id_mapping
+    }
+
+    /// The real id of the resource added under `temp_id`, e.g. the
+    /// [`Command::temp_id`] of an `item_add` command of the request.
+    pub fn resolve(&self, temp_id: &str) -> Option<u64> {
+        self.temp_id_mapping.get(temp_id)
+    }
+
+    /// Outcome of the commands of the request.
+    pub fn sync_status(&self) -> &SyncStatus {
+        &self.sync_status
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// The request could not be sent or the API answered with an error status.
+    Http(reqwest::Error),
+    /// The response body was not what the API is documented to send.
+    Json(serde_json::Error),
+    /// The response lacked a resource that was asked for.
+    Missing(&'static str),
+    /// The API refused a command.
+    Command { error_code: i64, error: String },
+    /// The cache the response was to be applied to failed.
+    Cache(CacheError),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::Http(err) => write!(f, "Request to the API failed: {}", err),
+            ClientError::Json(err) => write!(f, "Invalid response from the API: {}", err),
+            ClientError::Missing(resource) => write!(f, "The API did not send the {}", resource),
+            ClientError::Command { error_code, error } => {
+                write!(f, "Command failed with error {}: {}", error_code, error)
+            }
+            ClientError::Cache(err) => write!(f, "Cache failed: {}", err),
+        }
+    }
+}
+
+impl Error for ClientError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ClientError::Http(err) => Some(err),
+            ClientError::Json(err) => Some(err),
+            ClientError::Cache(err) => Some(err),
+            ClientError::Missing(_) | ClientError::Command { .. } => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        ClientError::Http(err)
+    }
+}
+
+impl From<serde_json::Error> for ClientError {
+    fn from(err: serde_json::Error) -> Self {
+        ClientError::Json(err)
+    }
+}
+
+impl From<CacheError> for ClientError {
+    fn from(err: CacheError) -> Self {
+        ClientError::Cache(err)
+    }
+}
+
+/// Access to the `sync` endpoint.
+///
+/// Kept as a trait so the session logic in [`TodoistClient`] can run against a
+/// canned implementation in tests.
+#[async_trait]
+pub trait SyncApi {
+    /// Reads the given resource types. A `sync_token` of `"*"` asks for a full
+    /// sync, anything else for the changes since that token was issued.
+    async fn sync(
+        &self,
+        sync_token: &str,
+        resource_types: &[&str],
+    ) -> Result<SyncResponse, ClientError>;
+
+    /// Sends commands changing resources.
+    async fn write(&self, commands: &[Command]) -> Result<SyncResponse, ClientError>;
+}
+
+/// Turns the first failed command of `response` into an error.
+fn check_status(response: &SyncResponse, commands: &[Command]) -> Result<(), ClientError> {
+    for command in commands {
+        if let Some(CommandStatus::Error { error_code, error }) =
+            response.sync_status.get(command.uuid())
+        {
+            return Err(ClientError::Command {
+                error_code: *error_code,
+                error: error.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Version of the data this crate models. Bumped whenever a change to the types
+/// means data synced by an earlier version is incomplete, so that sessions
+/// resumed from an older [`SyncState`] start over with a full sync.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// What a sync session needs to be resumed later, see
+/// [`TodoistClient::sync_state`] and [`TodoistClient::resume`].
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct SyncState {
+    sync_token: String,
+    schema_version: u32,
+}
+
+impl SyncState {
+    /// The state of a session at `sync_token` under the current
+    /// [`SCHEMA_VERSION`].
+    pub fn new(sync_token: &str) -> Self {
+        SyncState {
+            sync_token: sync_token.to_string(),
+            schema_version: SCHEMA_VERSION,
+        }
+    }
+
+    /// The sync token to resume from: the saved one, or `"*"` for a full sync
+    /// if it was saved under another schema version.
+    pub fn sync_token(&self) -> &str {
+        if self.schema_version == SCHEMA_VERSION {
+            &self.sync_token
+        } else {
+            "*"
+        }
+    }
+
+    pub fn schema_version(&self) -> u32 {
+        self.schema_version
+    }
+}
+
+/// A sync session keeping a local [`Store`] up to date.
+#[derive(Debug)]
+pub struct TodoistClient<A> {
+    api: A,
+    store: Store,
+}
+
+impl<A: SyncApi + Send + Sync> TodoistClient<A> {
+    pub fn new(api: A) -> Self {
+        TodoistClient {
+            api,
+            store: Store::new(),
+        }
+    }
+
+    /// Starts a session where a previous one left off, see
+    /// [`TodoistClient::sync_state`]. The next sync only fetches what changed
+    /// since, unless `state` is from another schema version.
+    pub fn resume(api: A, state: &SyncState) -> Self {
+        let mut store = Store::new();
+        store.set_sync_token(state.sync_token());
+        TodoistClient { api, store }
+    }
+
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    /// The state to save to [`resume`](TodoistClient::resume) this session
+    /// later.
+    pub fn sync_state(&self) -> SyncState {
+        SyncState::new(self.store.sync_token().unwrap_or("*"))
+    }
+
+    /// Runs a sync, incremental if a previous sync was applied, and merges it
+    /// into the store.
+    async fn sync(&mut self) -> Result<(), ClientError> {
+        let sync_token = self.store.sync_token().unwrap_or("*").to_string();
+        let response = self.api.sync(&sync_token, &["all"]).await?;
+        self.store.apply(response);
+        Ok(())
+    }
+
+    /// Brings the item with the given id up to date, e.g. after being notified
+    /// of a change by a webhook. The Sync API cannot be asked for a single
+    /// resource, so this is an incremental sync that also updates everything
+    /// else that changed.
+    ///
+    /// Gives `None` if the item does not exist or was deleted.
+    pub async fn refresh_item(&mut self, id: u64) -> Result<Option<&Item>, ClientError> {
+        self.sync().await?;
+        Ok(self.store.item(id))
+    }
+
+    /// Syncs until the item with the given id shows up, e.g. when a command
+    /// was written through another session, for at most `timeout`.
+    ///
+    /// Gives `None` if the item is still missing once `timeout` elapsed.
+    pub async fn wait_for_item(
+        &mut self,
+        id: u64,
+        timeout: Duration,
+    ) -> Result<Option<&Item>, ClientError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            self.sync().await?;
+            let now = Instant::now();
+            if self.store.item(id).is_some() || now >= deadline {
+                break;
+            }
+            time::sleep(POLL_INTERVAL.min(deadline - now)).await;
+        }
+        Ok(self.store.item(id))
+    }
+
+    /// Reads the user's account details and settings.
+    pub async fn get_user(&self) -> Result<User, ClientError> {
+        let response = self.api.sync("*", &["user"]).await?;
+        response.user.ok_or(ClientError::Missing("user"))
+    }
+
+    /// The people the project with the given id is shared with, including
+    /// those invited who did not join yet. Empty for projects that are not
+    /// shared.
+    pub async fn collaborators(&self, project_id: u64) -> Result<Vec<Collaborator>, ClientError> {
+        let response = self.api.sync("*", &["collaborators"]).await?;
+        let members: Vec<u64> = response
+            .collaborator_states
+            .iter()
+            .filter(|state| {
+                state.project_id() == project_id && state.state() != CollaboratorStatus::Deleted
+            })
+            .map(CollaboratorState::user_id)
+            .collect();
+        Ok(response
+            .collaborators
+            .into_iter()
+            .filter(|collaborator| members.contains(&collaborator.id()))
+            .collect())
+    }
+
+    /// Changes one of the user's settings, then syncs to pick up the change.
+    pub async fn update_user_setting(&mut self, setting: UserSetting) -> Result<(), ClientError> {
+        let commands = [Command::user_update(&setting)];
+        let response = self.api.write(&commands).await?;
+        check_status(&response, &commands)?;
+        self.sync().await
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Replays canned responses to reads and records the sync tokens and
+    /// commands it was sent. Every command succeeds.
+    pub(crate) struct MockApi {
+        pub(crate) responses: Mutex<Vec<&'static str>>,
+        pub(crate) tokens: Mutex<Vec<String>>,
+        pub(crate) commands: Mutex<Vec<Command>>,
+    }
+
+    impl MockApi {
+        pub(crate) fn new(responses: Vec<&'static str>) -> Self {
+            MockApi {
+                responses: Mutex::new(responses),
+                tokens: Mutex::new(Vec::new()),
+                commands: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl SyncApi for MockApi {
+        async fn sync(
+            &self,
+            sync_token: &str,
+            _resource_types: &[&str],
+        ) -> Result<SyncResponse, ClientError> {
+            self.tokens.lock().unwrap().push(sync_token.to_string());
+            let body = self.responses.lock().unwrap().remove(0);
+            Ok(serde_json::from_str(body)?)
+        }
+
+        async fn write(&self, commands: &[Command]) -> Result<SyncResponse, ClientError> {
+            self.commands.lock().unwrap().extend_from_slice(commands);
+            let status: HashMap<&str, &str> = commands.iter().map(|c| (c.uuid(), "ok")).collect();
+            Ok(serde_json::from_value(serde_json::json!({
+                "sync_token": "written",
+                "full_sync": false,
+                "sync_status": status,
+            }))?)
+        }
+    }
+
+    const USER: &str = r#"{
+        "sync_token": "user",
+        "full_sync": true,
+        "user": {
+            "id": 1, "email": "bob@example.com", "full_name": "Bob",
+            "tz_info": {"timezone": "Europe/Paris", "gmt_string": "+01:00",
+                        "hours": 1, "minutes": 0, "is_dst": 0},
+            "start_day": 1, "next_week": 1, "time_format": 0, "date_format": 0,
+            "lang": "en", "is_premium": true, "inbox_project": 2
+        }
+    }"#;
+
+    #[test]
+    fn sync_response_resources_test() {
+        let response: SyncResponse = serde_json::from_str(
+            r#"{
+                "sync_token": "abc",
+                "full_sync": true,
+                "items": [
+                    {"id": 7, "user_id": 1, "project_id": 2, "content": "Call Bob",
+                     "description": "", "due": null, "priority": 1,
+                     "parent_id": null, "child_order": 1, "section_id": 5,
+                     "labels": [], "checked": false, "is_deleted": false}
+                ],
+                "projects": [
+                    {"id": 2, "name": "Work", "color": 47, "parent_id": null,
+                     "child_order": 1, "collapsed": false, "shared": false,
+                     "is_deleted": false, "is_archived": false,
+                     "is_favorite": false, "inbox_project": false}
+                ],
+                "filters": [
+                    {"id": 4, "name": "Urgent", "query": "p1", "color": 30,
+                     "item_order": 1, "is_deleted": 0, "is_favorite": 0}
+                ],
+                "sections": [
+                    {"id": 5, "name": "Calls", "project_id": 2, "section_order": 1,
+                     "collapsed": false, "is_deleted": false, "is_archived": false}
+                ],
+                "notes": [
+                    {"id": 6, "item_id": 7, "content": "Ask about the invoice",
+                     "posted": "2021-03-01T10:00:00Z", "file_attachment": null,
+                     "is_deleted": false}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.sync_token(), "abc");
+        assert!(response.full_sync());
+        assert_eq!(response.items()[0].section_id(), Some(5));
+        assert_eq!(response.projects()[0].name(), "Work");
+        assert_eq!(response.filters()[0].query(), "p1");
+        assert_eq!(response.sections()[0].name(), "Calls");
+        assert_eq!(response.notes()[0].item_id(), Some(7));
+        assert!(response.labels().is_empty());
+        assert_eq!(response.user(), None);
+
+        let incremental: SyncResponse =
+            serde_json::from_str(r#"{"sync_token": "def", "full_sync": false}"#).unwrap();
+        assert!(incremental.notes().is_empty());
+        assert!(serde_json::from_str::<SyncResponse>(r#"{"full_sync": false}"#).is_err());
+    }
+
+    #[test]
+    fn sync_response_resolve_test() {
+        let command = Command::item_add(Item::builder().content("Buy milk").project_id(1));
+        let temp_id = command.temp_id().unwrap();
+        let body = format!(
+            r#"{{
+                "sync_token": "abc",
+                "full_sync": false,
+                "temp_id_mapping": {{"{}": 42}},
+                "sync_status": {{"{}": "ok"}}
+            }}"#,
+            temp_id,
+            command.uuid()
+        );
+        let response: SyncResponse = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(response.temp_id_mapping().len(), 1);
+        assert_eq!(response.resolve(temp_id), Some(42));
+        assert_eq!(response.resolve("unknown"), None);
+    }
+
+    #[test]
+    fn sync_response_commands_test() {
+        let response: SyncResponse = serde_json::from_str(
+            r#"{
+                "sync_token": "abc",
+                "full_sync": false,
+                "temp_id_mapping": {"43f7ed23-a038-46b5-b2c9-4abda9097ffa": 42},
+                "sync_status": {
+                    "ed1ce597-e4c7-4a88-ba48-e048d827c067": "ok",
+                    "d5d9a0d6-0a4c-4b3c-bd2b-6d3d2d5b5a5e": {
+                        "error_code": 20,
+                        "error": "Item not found"
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.resolve("43f7ed23-a038-46b5-b2c9-4abda9097ffa"),
+            Some(42)
+        );
+        let status = response.sync_status();
+        assert!(!status.is_ok());
+        assert!(status
+            .get("ed1ce597-e4c7-4a88-ba48-e048d827c067")
+            .unwrap()
+            .is_ok());
+        assert_eq!(
+            status.get("d5d9a0d6-0a4c-4b3c-bd2b-6d3d2d5b5a5e"),
+            Some(&CommandStatus::Error {
+                error_code: 20,
+                error: String::from("Item not found"),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_item_test() {
+        let api = MockApi::new(vec![
+            r#"{"sync_token": "first", "full_sync": true, "items": []}"#,
+            r#"{
+                "sync_token": "second",
+                "full_sync": false,
+                "items": [
+                    {"id": 7, "user_id": 1, "project_id": 2, "content": "Call Bob",
+                     "description": "", "due": null, "priority": 1,
+                     "parent_id": null, "child_order": 1, "section_id": null,
+                     "labels": [], "checked": false, "is_deleted": false}
+                ]
+            }"#,
+        ]);
+        let mut client = TodoistClient::new(api);
+
+        assert_eq!(client.refresh_item(7).await.unwrap(), None);
+        let item = client.refresh_item(7).await.unwrap().unwrap();
+        assert_eq!(item.id(), Some(7));
+        assert_eq!(client.store().item(7).unwrap().project_id(), 2);
+        assert_eq!(*client.api.tokens.lock().unwrap(), vec!["*", "first"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn wait_for_item_test() {
+        let api = MockApi::new(vec![
+            r#"{"sync_token": "first", "full_sync": true, "items": []}"#,
+            r#"{
+                "sync_token": "second",
+                "full_sync": false,
+                "items": [
+                    {"id": 7, "user_id": 1, "project_id": 2, "content": "Call Bob",
+                     "description": "", "due": null, "priority": 1,
+                     "parent_id": null, "child_order": 1, "section_id": null,
+                     "labels": [], "checked": false, "is_deleted": false}
+                ]
+            }"#,
+            r#"{"sync_token": "third", "full_sync": false, "items": []}"#,
+            r#"{"sync_token": "fourth", "full_sync": false, "items": []}"#,
+        ]);
+        let mut client = TodoistClient::new(api);
+
+        let start = Instant::now();
+        let item = client
+            .wait_for_item(7, Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert_eq!(item.unwrap().content(), "Call Bob");
+        assert_eq!(start.elapsed(), POLL_INTERVAL);
+        assert_eq!(*client.api.tokens.lock().unwrap(), vec!["*", "first"]);
+
+        let missing = client
+            .wait_for_item(8, Duration::from_millis(500))
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+        assert_eq!(start.elapsed(), POLL_INTERVAL + Duration::from_millis(500));
+        assert_eq!(client.api.tokens.lock().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn sync_state_serde_test() {
+        let state = SyncState::new("abc");
+        let json = serde_json::to_value(&state).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({ "sync_token": "abc", "schema_version": SCHEMA_VERSION })
+        );
+        assert_eq!(serde_json::from_value::<SyncState>(json).unwrap(), state);
+    }
+
+    #[tokio::test]
+    async fn sync_state_resume_test() {
+        let response = r#"{"sync_token": "next", "full_sync": true}"#;
+        let current = SyncState::new("saved");
+        let mut client = TodoistClient::resume(MockApi::new(vec![response]), &current);
+        assert_eq!(client.sync_state(), current);
+        client.refresh_item(7).await.unwrap();
+        assert_eq!(*client.api.tokens.lock().unwrap(), vec!["saved"]);
+        assert_eq!(client.sync_state().sync_token(), "next");
+
+        let stale: SyncState = serde_json::from_value(serde_json::json!({
+            "sync_token": "saved",
+            "schema_version": SCHEMA_VERSION - 1,
+        }))
+        .unwrap();
+        assert_eq!(stale.sync_token(), "*");
+        let mut client = TodoistClient::resume(MockApi::new(vec![response]), &stale);
+        assert_eq!(client.sync_state().sync_token(), "*");
+        client.refresh_item(7).await.unwrap();
+        assert_eq!(*client.api.tokens.lock().unwrap(), vec!["*"]);
+    }
+
+    #[tokio::test]
+    async fn get_user_test() {
+        let client = TodoistClient::new(MockApi::new(vec![
+            USER,
+            r#"{"sync_token": "none", "full_sync": true}"#,
+        ]));
+
+        let user = client.get_user().await.unwrap();
+        assert_eq!(user.full_name(), "Bob");
+        assert_eq!(user.start_day(), 1);
+        assert!(matches!(
+            client.get_user().await,
+            Err(ClientError::Missing("user"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn update_user_setting_test() {
+        let mut client = TodoistClient::new(MockApi::new(vec![USER]));
+
+        client
+            .update_user_setting(UserSetting::TimeFormat(1))
+            .await
+            .unwrap();
+
+        let commands = client.api.commands.lock().unwrap();
+        assert_eq!(commands.len(), 1);
+        let json = serde_json::to_value(&commands[0]).unwrap();
+        assert_eq!(json["type"], serde_json::json!("user_update"));
+        assert_eq!(json["args"], serde_json::json!({ "time_format": 1 }));
+        assert_eq!(client.store().sync_token(), Some("user"));
+    }
+
+    #[tokio::test]
+    async fn collaborators_test() {
+        let client = TodoistClient::new(MockApi::new(vec![
+            r#"{
+                "sync_token": "shared",
+                "full_sync": true,
+                "collaborators": [
+                    {"id": 1, "email": "alice@example.com", "full_name": "Alice"},
+                    {"id": 2, "email": "bob@example.com", "full_name": "Bob"},
+                    {"id": 3, "email": "carol@example.com", "full_name": "Carol"}
+                ],
+                "collaborator_states": [
+                    {"project_id": 10, "user_id": 1, "state": "active"},
+                    {"project_id": 10, "user_id": 2, "state": "deleted"},
+                    {"project_id": 20, "user_id": 3, "state": "invited"}
+                ]
+            }"#,
+        ]));
+
+        let collaborators = client.collaborators(10).await.unwrap();
+        assert_eq!(collaborators.len(), 1);
+        assert_eq!(collaborators[0].full_name(), "Alice");
+        assert_eq!(collaborators[0].email(), "alice@example.com");
+    }
+}
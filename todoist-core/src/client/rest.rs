@@ -0,0 +1,86 @@
+//! Endpoints of the Sync API outside of the command based `sync` endpoint.
+//!
+//! Some operations are simpler to do with a single plain request, at the cost
+//! of not being batched with other changes. These are still Sync API
+//! endpoints: the REST API v2 has no quick add, so its tasks, see
+//! [`Item::from_rest_v2`], are never requested here.
+use crate::client::{ClientError, BASE_URL};
+use crate::types::items::Item;
+
+/// Sends single requests to the Sync API, next to the commands of
+/// [`crate::client::Client`].
+pub struct RestClient {
+    http: reqwest::Client,
+    token: String,
+    base_url: String,
+}
+
+impl RestClient {
+    /// Creates a client authenticating with the user's API token.
+    pub fn new(token: &str) -> Self {
+        Self::with_base_url(token, BASE_URL)
+    }
+
+    /// Creates a client sending its requests to `base_url` instead of the
+    /// Todoist servers, e.g. a local mock.
+    pub fn with_base_url(token: &str, base_url: &str) -> Self {
+        RestClient {
+            http: reqwest::Client::new(),
+            token: token.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Adds a task the way the quick add box of the apps does, with the Sync
+    /// API's `quick/add` endpoint: `text` is parsed by the server, so
+    /// `#Project`, `@label`, `p1` and due dates like `tomorrow` in it are
+    /// turned into the matching fields. The added item is returned as the
+    /// Sync API sends items.
+    pub async fn quick_add(&self, text: &str) -> Result<Item, ClientError> {
+        let body = self
+            .http
+            .post(format!("{}/quick/add", self.base_url))
+            .bearer_auth(&self.token)
+            .form(&[("text", text)])
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        Ok(serde_json::from_str(&body)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::priority::Priority;
+    use wiremock::matchers::{body_string_contains, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn quick_add_test() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/quick/add"))
+            .and(header("authorization", "Bearer secret"))
+            .and(body_string_contains("text=Call+Bob"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"id": 7, "user_id": 1, "project_id": 2, "content": "Call Bob",
+                    "description": "", "due": null, "priority": 4,
+                    "parent_id": null, "child_order": 1, "section_id": null,
+                    "labels": [3], "checked": false, "is_deleted": false,
+                    "date_added": "2021-03-01T10:00:00Z"}"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = RestClient::with_base_url("secret", &server.uri());
+        let item = client.quick_add("Call Bob #Work @phone p1").await.unwrap();
+        assert_eq!(item.id(), Some(7));
+        assert_eq!(item.project_id(), 2);
+        assert_eq!(item.priority(), &Priority::P1);
+        assert_eq!(item.labels(), &[3]);
+    }
+}
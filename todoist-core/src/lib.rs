@@ -1,3 +1,46 @@
+pub mod cache;
+pub mod client;
+mod pending;
+pub mod store;
+pub mod types;
+#[cfg(feature = "yaml")]
+pub mod yaml;
+
+pub use pending::PendingEdit;
+pub use types::colors::colors_in_use;
+
+/// Runs `f` and returns everything it logged through `tracing`.
+#[cfg(test)]
+pub(crate) fn capture_logs<F: FnOnce()>(f: F) -> String {
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let buffer = Buffer::default();
+    let writer = buffer.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(move || writer.clone())
+        .with_ansi(false)
+        .without_time()
+        .finish();
+    tracing::subscriber::with_default(subscriber, f);
+
+    let logs = buffer.0.lock().unwrap();
+    String::from_utf8_lossy(&logs).into_owned()
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
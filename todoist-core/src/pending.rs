@@ -0,0 +1,69 @@
+//! Coalescing of item edits made in quick succession.
+use std::collections::BTreeMap;
+
+use crate::client::Command;
+use crate::types::items::{Item, ItemBuilder};
+
+/// Collects edits to items until they are flushed, so a burst of changes to
+/// the same item, e.g. from an interactive editor, is sent as a single
+/// `item_update` command. When a field is edited more than once, the last
+/// value wins.
+#[derive(Debug, Default)]
+pub struct PendingEdit {
+    edits: BTreeMap<u64, ItemBuilder>,
+}
+
+impl PendingEdit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The pending edit of the item with the given id, to set the changed
+    /// fields on.
+    pub fn edit(&mut self, id: u64) -> &mut ItemBuilder {
+        self.edits.entry(id).or_insert_with(|| {
+            let mut builder = Item::builder();
+            builder.id(id);
+            builder
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Turns the pending edits into one `item_update` command per item, in
+    /// item id order, and forgets them.
+    pub fn flush(&mut self) -> Vec<Command> {
+        std::mem::take(&mut self.edits)
+            .values()
+            .map(|builder| Command::item_update(builder).expect("pending edits have an id"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::priority::Priority;
+    use serde_json::json;
+
+    #[test]
+    fn pending_edit_flush_test() {
+        let mut pending = PendingEdit::new();
+        pending.edit(7).content("Call Bob");
+        pending.edit(7).priority(Priority::P1);
+        pending.edit(7).content("Call Bob back");
+
+        let commands = pending.flush();
+        assert_eq!(commands.len(), 1);
+        let json = serde_json::to_value(&commands[0]).unwrap();
+        assert_eq!(json["type"], json!("item_update"));
+        assert_eq!(
+            json["args"],
+            json!({ "id": 7, "content": "Call Bob back", "priority": 4 })
+        );
+        assert!(pending.is_empty());
+        assert!(pending.flush().is_empty());
+    }
+}
@@ -0,0 +1,502 @@
+//! Local copy of the user's resources, kept up to date with sync responses.
+use std::collections::HashMap;
+
+#[cfg(feature = "chrono")]
+use chrono::NaiveDate;
+
+use crate::client::SyncResponse;
+#[cfg(feature = "chrono")]
+use crate::types::filters::{self, Filter, FilterError};
+use crate::types::items::Item;
+use crate::types::labels::Label;
+#[cfg(feature = "chrono")]
+use crate::types::labels::LabelIndex;
+use crate::types::projects::Project;
+#[cfg(feature = "chrono")]
+use crate::types::projects::ProjectIndex;
+
+/// Distance between the id ranges of two namespaces in [`Store::merge`].
+/// Todoist ids are far below it.
+const NAMESPACE_STRIDE: u64 = 1 << 48;
+
+/// Ids of the resources of one type that differ between two stores.
+#[derive(Debug, Default, PartialEq)]
+pub struct EntityDiff {
+    /// Only in the newer store.
+    pub added: Vec<u64>,
+    /// Only in the older store.
+    pub removed: Vec<u64>,
+    /// In both stores but with different fields.
+    pub changed: Vec<u64>,
+}
+
+impl EntityDiff {
+    fn between<T: PartialEq>(old: &HashMap<u64, T>, new: &HashMap<u64, T>) -> Self {
+        let mut diff = EntityDiff::default();
+        for (id, entity) in new {
+            match old.get(id) {
+                None => diff.added.push(*id),
+                Some(previous) if previous != entity => diff.changed.push(*id),
+                Some(_) => {}
+            }
+        }
+        diff.removed = old
+            .keys()
+            .filter(|id| !new.contains_key(id))
+            .copied()
+            .collect();
+
+        diff.added.sort_unstable();
+        diff.removed.sort_unstable();
+        diff.changed.sort_unstable();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Differences between two snapshots of a store, see [`Store::diff`].
+#[derive(Debug, Default, PartialEq)]
+pub struct StoreDiff {
+    pub items: EntityDiff,
+    pub projects: EntityDiff,
+    pub labels: EntityDiff,
+}
+
+impl StoreDiff {
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty() && self.projects.is_empty() && self.labels.is_empty()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Store {
+    sync_token: Option<String>,
+    items: HashMap<u64, Item>,
+    projects: HashMap<u64, Project>,
+    labels: HashMap<u64, Label>,
+    inbox_id: Option<u64>,
+}
+
+impl Store {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merges a sync response into the store.
+    ///
+    /// A full sync replaces everything, an incremental one only updates the
+    /// resources it carries. Resources flagged as deleted are dropped. Day
+    /// orders, which the API sends apart from the items, are set on the items.
+    pub fn apply(&mut self, response: SyncResponse) {
+        if response.full_sync {
+            self.items.clear();
+            self.projects.clear();
+            self.labels.clear();
+            self.inbox_id = None;
+        }
+
+        for item in response.items {
+            let id = match item.id() {
+                Some(id) => id,
+                None => continue,
+            };
+            if item.is_deleted() {
+                self.items.remove(&id);
+            } else {
+                self.items.insert(id, item);
+            }
+        }
+        for (id, order) in response.day_orders {
+            if let Some(item) = self.items.get_mut(&id) {
+                item.set_day_order(order);
+            }
+        }
+
+        for project in response.projects {
+            let id = match project.id() {
+                Some(id) => id,
+                None => continue,
+            };
+            if project.is_deleted() {
+                self.projects.remove(&id);
+                if self.inbox_id == Some(id) {
+                    self.inbox_id = None;
+                }
+                continue;
+            }
+            if project.inbox_project() {
+                self.inbox_id = Some(id);
+            }
+            self.projects.insert(id, project);
+        }
+
+        for label in response.labels {
+            let id = match label.id() {
+                Some(id) => id,
+                None => continue,
+            };
+            if label.is_deleted() {
+                self.labels.remove(&id);
+            } else {
+                self.labels.insert(id, label);
+            }
+        }
+
+        self.sync_token = Some(response.sync_token);
+    }
+
+    /// Imports the resources of `other`, e.g. the store of a second account,
+    /// for a unified view over both.
+    ///
+    /// Ids of different accounts can collide, so the ids of `other`'s resources,
+    /// and the ids they reference, are moved to their own range by adding
+    /// `namespace * 2^48`. Namespace 0 leaves them untouched, which is what the
+    /// store's own resources use. The sync token and inbox of `self` are kept.
+    pub fn merge(&mut self, other: Store, namespace: u64) {
+        let offset = namespace.wrapping_mul(NAMESPACE_STRIDE);
+
+        for (id, mut item) in other.items {
+            item.offset_ids(offset);
+            self.items.insert(id.wrapping_add(offset), item);
+        }
+        for (id, mut project) in other.projects {
+            project.offset_ids(offset);
+            self.projects.insert(id.wrapping_add(offset), project);
+        }
+        for (id, mut label) in other.labels {
+            label.offset_ids(offset);
+            self.labels.insert(id.wrapping_add(offset), label);
+        }
+        if self.inbox_id.is_none() {
+            self.inbox_id = other.inbox_id.map(|id| id.wrapping_add(offset));
+        }
+    }
+
+    /// What changed going from `self` to `other`, e.g. to compare the store
+    /// before and after a sync. Resources are compared field by field.
+    pub fn diff(&self, other: &Store) -> StoreDiff {
+        StoreDiff {
+            items: EntityDiff::between(&self.items, &other.items),
+            projects: EntityDiff::between(&self.projects, &other.projects),
+            labels: EntityDiff::between(&self.labels, &other.labels),
+        }
+    }
+
+    /// Token to send with the next incremental sync, if a sync was applied.
+    pub fn sync_token(&self) -> Option<&str> {
+        self.sync_token.as_deref()
+    }
+
+    /// Sets the token the next sync starts from. `"*"` stands for no token.
+    pub(crate) fn set_sync_token(&mut self, sync_token: &str) {
+        self.sync_token = Some(sync_token.to_string()).filter(|token| token != "*");
+    }
+
+    pub fn item(&self, id: u64) -> Option<&Item> {
+        self.items.get(&id)
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = &Item> {
+        self.items.values()
+    }
+
+    pub fn project(&self, id: u64) -> Option<&Project> {
+        self.projects.get(&id)
+    }
+
+    pub fn projects(&self) -> impl Iterator<Item = &Project> {
+        self.projects.values()
+    }
+
+    pub fn label(&self, id: u64) -> Option<&Label> {
+        self.labels.get(&id)
+    }
+
+    pub fn labels(&self) -> impl Iterator<Item = &Label> {
+        self.labels.values()
+    }
+
+    /// The uncompleted items matching `filter`'s query on the day `today`,
+    /// sorted by id. `@label` and `#Project` terms are resolved with the
+    /// store's labels and projects, and recurring tasks whose date has passed
+    /// count as overdue, like in the apps.
+    #[cfg(feature = "chrono")]
+    pub fn filter(&self, filter: &Filter, today: NaiveDate) -> Result<Vec<&Item>, FilterError> {
+        let query = filters::compile(
+            filter.query(),
+            &LabelIndex::new(self.labels.values()),
+            &ProjectIndex::new(self.projects.values()),
+        )?;
+        let mut items: Vec<&Item> = self
+            .items
+            .values()
+            .filter(|item| !item.checked() && filters::evaluate(&query, item, today, true))
+            .collect();
+        items.sort_unstable_by_key(|item| item.id());
+        Ok(items)
+    }
+
+    /// Id of the user's inbox project, known once a sync containing it was applied.
+    pub fn inbox_id(&self) -> Option<u64> {
+        self.inbox_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(items: Vec<Item>, projects: Vec<Project>) -> SyncResponse {
+        SyncResponse {
+            sync_token: String::from("token"),
+            full_sync: true,
+            items,
+            projects,
+            labels: Vec::new(),
+            filters: Vec::new(),
+            sections: Vec::new(),
+            notes: Vec::new(),
+            collaborators: Vec::new(),
+            collaborator_states: Vec::new(),
+            day_orders: HashMap::new(),
+            user: None,
+            temp_id_mapping: Default::default(),
+            sync_status: Default::default(),
+        }
+    }
+
+    #[test]
+    fn store_inbox_id_test() {
+        let mut store = Store::new();
+        assert_eq!(store.inbox_id(), None);
+
+        let response: SyncResponse = serde_json::from_str(
+            r#"{
+                "sync_token": "abc",
+                "full_sync": true,
+                "projects": [
+                    {"id": 1, "name": "Inbox", "color": 48, "parent_id": null,
+                     "child_order": 0, "collapsed": false, "is_deleted": false,
+                     "is_archived": false, "is_favorite": false, "inbox_project": true},
+                    {"id": 2, "name": "Work", "color": 41, "parent_id": null,
+                     "child_order": 1, "collapsed": false, "is_deleted": false,
+                     "is_archived": false, "is_favorite": false}
+                ]
+            }"#,
+        )
+        .unwrap();
+        store.apply(response);
+
+        assert_eq!(store.inbox_id(), Some(1));
+        assert_eq!(store.sync_token(), Some("abc"));
+        assert_eq!(store.project(2).unwrap().name(), "Work");
+    }
+
+    #[test]
+    fn store_merge_test() {
+        let mut personal = Store::new();
+        personal.apply(response(
+            vec![Item::builder()
+                .id(1)
+                .user_id(100)
+                .project_id(1)
+                .content("Buy milk")
+                .build()
+                .unwrap()],
+            vec![Project::builder().id(1).name("Home").build().unwrap()],
+        ));
+        let mut work = Store::new();
+        let mut sync = response(
+            vec![Item::builder()
+                .id(1)
+                .user_id(200)
+                .project_id(1)
+                .content("Write report")
+                .label_add(5)
+                .build()
+                .unwrap()],
+            vec![Project::builder().id(1).name("Office").build().unwrap()],
+        );
+        sync.labels = vec![Label::builder().id(5).name("Urgent").build().unwrap()];
+        work.apply(sync);
+
+        personal.merge(work, 1);
+
+        assert_eq!(personal.items().count(), 2);
+        assert_eq!(personal.projects().count(), 2);
+        let moved = personal.item(1 + NAMESPACE_STRIDE).unwrap();
+        assert_eq!(moved.id(), Some(1 + NAMESPACE_STRIDE));
+        let project = personal.project(moved.project_id()).unwrap();
+        assert_eq!(project.name(), "Office");
+        let label = personal.label(moved.labels()[0]).unwrap();
+        assert_eq!(label.name(), "Urgent");
+        assert_eq!(label.id(), Some(5 + NAMESPACE_STRIDE));
+        assert_eq!(
+            personal
+                .project(personal.item(1).unwrap().project_id())
+                .unwrap()
+                .name(),
+            "Home"
+        );
+    }
+
+    #[test]
+    fn store_diff_test() {
+        let item = |id: u64, content: &str| {
+            Item::builder()
+                .id(id)
+                .user_id(1)
+                .project_id(1)
+                .content(content)
+                .build()
+                .unwrap()
+        };
+        let project = || Project::builder().id(1).name("Home").build().unwrap();
+        let label = |name: &str| Label::builder().id(7).name(name).build().unwrap();
+
+        let mut before = Store::new();
+        let mut sync = response(
+            vec![item(1, "Buy milk"), item(2, "Walk the dog")],
+            vec![project()],
+        );
+        sync.labels = vec![label("Errand")];
+        before.apply(sync);
+        let mut after = Store::new();
+        let mut sync = response(
+            vec![
+                item(1, "Buy oat milk"),
+                item(2, "Walk the dog"),
+                item(3, "Call Bob"),
+            ],
+            vec![project()],
+        );
+        sync.labels = vec![label("Errands")];
+        after.apply(sync);
+
+        let diff = before.diff(&after);
+        assert_eq!(
+            diff.items,
+            EntityDiff {
+                added: vec![3],
+                removed: vec![],
+                changed: vec![1],
+            }
+        );
+        assert!(diff.projects.is_empty());
+        assert_eq!(diff.labels.changed, vec![7]);
+        assert_eq!(after.diff(&before).items.removed, vec![3]);
+        assert!(before.diff(&before).is_empty());
+    }
+
+    #[test]
+    fn store_day_orders_test() {
+        let item = |id: u64| {
+            Item::builder()
+                .id(id)
+                .user_id(1)
+                .project_id(1)
+                .content("Buy milk")
+                .build()
+                .unwrap()
+        };
+        let mut store = Store::new();
+        let mut sync = response(vec![item(1), item(2)], vec![]);
+        sync.day_orders = vec![(1, 3), (9, 1)].into_iter().collect();
+
+        store.apply(sync);
+
+        assert_eq!(store.item(1).unwrap().day_order(), 3);
+        assert_eq!(store.item(2).unwrap().day_order(), -1);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn store_filter_test() {
+        use crate::types::dates::DueDate;
+
+        let today = NaiveDate::from_ymd_opt(2021, 4, 7).unwrap();
+        let due = |date: &str| DueDate::builder().date(date).build().unwrap();
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(1).content("Task");
+        let items = vec![
+            builder
+                .id(1)
+                .label_add(10)
+                .due(due("2021-04-07"))
+                .build()
+                .unwrap(),
+            builder.id(2).due(due("2021-04-08")).build().unwrap(),
+            builder
+                .id(3)
+                .label_remove(10)
+                .due(due("2021-04-07"))
+                .build()
+                .unwrap(),
+            builder.id(4).label_add(10).checked(true).build().unwrap(),
+            builder
+                .id(5)
+                .project_id(2)
+                .label_remove(10)
+                .checked(false)
+                .build()
+                .unwrap(),
+        ];
+        let projects = vec![
+            Project::builder().id(1).name("Work").build().unwrap(),
+            Project::builder().id(2).name("Home").build().unwrap(),
+        ];
+        let mut sync = response(items, projects);
+        sync.labels = vec![Label::builder().id(10).name("Work").build().unwrap()];
+        let mut store = Store::new();
+        store.apply(sync);
+
+        let filter = Filter::builder()
+            .name("Work today")
+            .query("@work & today")
+            .build()
+            .unwrap();
+        let ids: Vec<Option<u64>> = store
+            .filter(&filter, today)
+            .unwrap()
+            .iter()
+            .map(|item| item.id())
+            .collect();
+        assert_eq!(ids, vec![Some(1)]);
+
+        let project = Filter::builder()
+            .name("Work project today")
+            .query("#work & today")
+            .build()
+            .unwrap();
+        let ids: Vec<Option<u64>> = store
+            .filter(&project, today)
+            .unwrap()
+            .iter()
+            .map(|item| item.id())
+            .collect();
+        assert_eq!(ids, vec![Some(1), Some(3)]);
+
+        let unknown = Filter::builder()
+            .name("Home")
+            .query("@home")
+            .build()
+            .unwrap();
+        assert_eq!(
+            store.filter(&unknown, today),
+            Err(FilterError::UnknownLabel(String::from("home")))
+        );
+
+        let project = Filter::builder()
+            .name("Office")
+            .query("#Office")
+            .build()
+            .unwrap();
+        assert_eq!(
+            store.filter(&project, today),
+            Err(FilterError::UnknownProject(String::from("Office")))
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//! People shared projects are shared with.
+use serde::{Deserialize, Serialize};
+
+/// A user some project of the current user is shared with. Which projects is
+/// told by [`CollaboratorState`]s.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Collaborator {
+    id: u64,
+    email: String,
+    full_name: String,
+}
+
+impl Collaborator {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+
+    pub fn full_name(&self) -> &str {
+        &self.full_name
+    }
+}
+
+/// Whether a collaborator takes part in a project.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum CollaboratorStatus {
+    Active,
+    /// Invited but not joined yet.
+    Invited,
+    /// Left or was removed from the project.
+    Deleted,
+}
+
+/// Membership of a collaborator in a project.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct CollaboratorState {
+    project_id: u64,
+    user_id: u64,
+    state: CollaboratorStatus,
+}
+
+impl CollaboratorState {
+    pub fn project_id(&self) -> u64 {
+        self.project_id
+    }
+
+    pub fn user_id(&self) -> u64 {
+        self.user_id
+    }
+
+    pub fn state(&self) -> CollaboratorStatus {
+        self.state
+    }
+}
@@ -0,0 +1,299 @@
+//! The fixed palette of colors projects, labels and filters can use.
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::types::filters::Filter;
+use crate::types::labels::Label;
+use crate::types::projects::Project;
+
+/// One of the colors supported by Todoist, with its API id as discriminant.
+/// Serialized as that id.
+#[derive(Serialize, Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[serde(into = "u8")]
+pub enum Colors {
+    BerryRed = 30,
+    Red = 31,
+    Orange = 32,
+    Yellow = 33,
+    OliveGreen = 34,
+    LimeGreen = 35,
+    Green = 36,
+    MintGreen = 37,
+    Teal = 38,
+    SkyBlue = 39,
+    LightBlue = 40,
+    Blue = 41,
+    Grape = 42,
+    Violet = 43,
+    Lavender = 44,
+    Magenta = 45,
+    Salmon = 46,
+    #[default]
+    Charcoal = 47,
+    Grey = 48,
+    Taupe = 49,
+}
+
+/// Every color with its hex code in the Todoist apps, in discriminant order.
+const PALETTE: [(Colors, &str); 20] = [
+    (Colors::BerryRed, "#b8256f"),
+    (Colors::Red, "#db4035"),
+    (Colors::Orange, "#ff9933"),
+    (Colors::Yellow, "#fad000"),
+    (Colors::OliveGreen, "#afb83b"),
+    (Colors::LimeGreen, "#7ecc49"),
+    (Colors::Green, "#299438"),
+    (Colors::MintGreen, "#6accbc"),
+    (Colors::Teal, "#158fad"),
+    (Colors::SkyBlue, "#14aaf5"),
+    (Colors::LightBlue, "#96c3eb"),
+    (Colors::Blue, "#4073ff"),
+    (Colors::Grape, "#884dff"),
+    (Colors::Violet, "#af38eb"),
+    (Colors::Lavender, "#eb96eb"),
+    (Colors::Magenta, "#e05194"),
+    (Colors::Salmon, "#ff8d85"),
+    (Colors::Charcoal, "#808080"),
+    (Colors::Grey, "#b8b8b8"),
+    (Colors::Taupe, "#ccac93"),
+];
+
+/// Parses `#rrggbb` or `rrggbb` into its components.
+fn parse_hex(hex: &str) -> Option<[u8; 3]> {
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+impl Colors {
+    /// Every color, in discriminant order, e.g. for a color picker.
+    pub fn all() -> [Colors; 20] {
+        let mut all = [Colors::default(); 20];
+        for (color, (palette, _)) in all.iter_mut().zip(PALETTE.iter()) {
+            *color = *palette;
+        }
+        all
+    }
+
+    /// The `#rrggbb` code the Todoist apps show this color with.
+    pub fn hex(&self) -> &'static str {
+        PALETTE[*self as usize - Colors::BerryRed as usize].1
+    }
+
+    /// Snaps an arbitrary `#rrggbb` color to the closest color of the palette,
+    /// measured as the euclidean distance in RGB space.
+    ///
+    /// Strings that are not a valid hex color give the default color,
+    /// `Colors::Charcoal`.
+    pub fn nearest(hex: &str) -> Colors {
+        match parse_hex(hex) {
+            Some(rgb) => Colors::nearest_rgb(rgb),
+            None => Colors::default(),
+        }
+    }
+
+    /// Snaps a color of the 256 color xterm palette, as used by terminal
+    /// themes, to the closest color of the palette, like [`Colors::nearest`].
+    pub fn from_ansi256(idx: u8) -> Colors {
+        const SYSTEM: [[u8; 3]; 16] = [
+            [0x00, 0x00, 0x00],
+            [0x80, 0x00, 0x00],
+            [0x00, 0x80, 0x00],
+            [0x80, 0x80, 0x00],
+            [0x00, 0x00, 0x80],
+            [0x80, 0x00, 0x80],
+            [0x00, 0x80, 0x80],
+            [0xc0, 0xc0, 0xc0],
+            [0x80, 0x80, 0x80],
+            [0xff, 0x00, 0x00],
+            [0x00, 0xff, 0x00],
+            [0xff, 0xff, 0x00],
+            [0x00, 0x00, 0xff],
+            [0xff, 0x00, 0xff],
+            [0x00, 0xff, 0xff],
+            [0xff, 0xff, 0xff],
+        ];
+        // Indices 16 to 231 are a 6x6x6 color cube, the rest a gray ramp.
+        let level = |step: u8| if step == 0 { 0 } else { 55 + 40 * step };
+        let rgb = match idx {
+            0..=15 => SYSTEM[usize::from(idx)],
+            16..=231 => {
+                let cube = idx - 16;
+                [level(cube / 36), level(cube / 6 % 6), level(cube % 6)]
+            }
+            _ => {
+                let gray = 8 + 10 * (idx - 232);
+                [gray, gray, gray]
+            }
+        };
+        Colors::nearest_rgb(rgb)
+    }
+
+    fn nearest_rgb(rgb: [u8; 3]) -> Colors {
+        let distance = |candidate: &str| -> u32 {
+            let other = parse_hex(candidate).expect("palette colors are valid hex");
+            rgb.iter()
+                .zip(other.iter())
+                .map(|(a, b)| (i32::from(*a) - i32::from(*b)).pow(2) as u32)
+                .sum()
+        };
+
+        PALETTE
+            .iter()
+            .min_by_key(|(_, candidate)| distance(candidate))
+            .map(|(color, _)| *color)
+            .unwrap_or_default()
+    }
+}
+
+/// Every color used by at least one of the given resources, once each and in
+/// discriminant order, e.g. to build a legend.
+pub fn colors_in_use(projects: &[Project], labels: &[Label], filters: &[Filter]) -> Vec<Colors> {
+    let mut colors: Vec<Colors> = projects
+        .iter()
+        .map(Project::color)
+        .chain(labels.iter().map(Label::color))
+        .chain(filters.iter().map(Filter::color))
+        .collect();
+    colors.sort_unstable_by_key(|color| *color as u8);
+    colors.dedup();
+    colors
+}
+
+impl From<Colors> for u8 {
+    fn from(color: Colors) -> u8 {
+        color as u8
+    }
+}
+
+/// A number that is not the API id of any color.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct InvalidColor(pub u8);
+
+impl fmt::Display for InvalidColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid color {}, expected 30 to 49", self.0)
+    }
+}
+
+impl Error for InvalidColor {}
+
+/// Converts from the API id, which is also the discriminant.
+impl TryFrom<u8> for Colors {
+    type Error = InvalidColor;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        PALETTE
+            .iter()
+            .map(|(color, _)| *color)
+            .find(|color| *color as u8 == value)
+            .ok_or(InvalidColor(value))
+    }
+}
+
+impl<'de> Deserialize<'de> for Colors {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::types::discriminant(deserializer, "color")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::error::BuilderError;
+    use serde_json::json;
+
+    #[test]
+    fn colors_nearest_test() {
+        assert_eq!(Colors::nearest("#db4035"), Colors::Red);
+        assert_eq!(Colors::nearest("#dd4433"), Colors::Red);
+        assert_eq!(Colors::nearest("4070f0"), Colors::Blue);
+        assert_eq!(Colors::nearest("#7f7f7f"), Colors::Charcoal);
+        assert_eq!(Colors::nearest("not a color"), Colors::Charcoal);
+    }
+
+    #[test]
+    fn colors_from_ansi256_test() {
+        // 160 is #d70000 in the color cube.
+        assert!(matches!(
+            Colors::from_ansi256(160),
+            Colors::Red | Colors::BerryRed
+        ));
+        assert_eq!(Colors::from_ansi256(12), Colors::nearest("#0000ff"));
+        assert_eq!(Colors::from_ansi256(244), Colors::nearest("#808080"));
+    }
+
+    #[test]
+    fn colors_api_id_test() {
+        for color in Colors::all().iter() {
+            assert_eq!(Colors::try_from(*color as u8), Ok(*color));
+            assert_eq!(Colors::nearest(color.hex()), *color);
+        }
+        assert_eq!(Colors::BerryRed.hex(), "#b8256f");
+        assert_eq!(Colors::Taupe.hex(), "#ccac93");
+        assert_eq!(Colors::try_from(29), Err(InvalidColor(29)));
+        assert_eq!(Colors::try_from(50), Err(InvalidColor(50)));
+    }
+
+    #[test]
+    fn colors_unknown_discriminant_test() {
+        let expected = BuilderError::UnknownDiscriminant {
+            field: "color",
+            value: 99,
+        };
+        let error = serde_json::from_value::<Colors>(json!(99)).unwrap_err();
+        assert_eq!(error.to_string(), expected.to_string());
+
+        let error = serde_json::from_value::<Project>(json!({
+            "id": 1,
+            "name": "Home",
+            "color": 99
+        }))
+        .unwrap_err();
+        assert!(error.to_string().starts_with("Unknown color 99"));
+
+        assert_eq!(
+            serde_json::from_value::<Colors>(json!(41)).unwrap(),
+            Colors::Blue
+        );
+    }
+
+    #[test]
+    fn colors_in_use_test() {
+        let projects = vec![
+            Project::builder()
+                .name("Home")
+                .color(Colors::Teal)
+                .build()
+                .unwrap(),
+            Project::builder()
+                .name("Work")
+                .color(Colors::Red)
+                .build()
+                .unwrap(),
+        ];
+        let labels = vec![Label::builder()
+            .name("urgent")
+            .color(Colors::Red)
+            .build()
+            .unwrap()];
+        let filters = vec![Filter::builder()
+            .name("Today")
+            .query("today")
+            .color(Colors::Teal)
+            .build()
+            .unwrap()];
+
+        assert_eq!(
+            colors_in_use(&projects, &labels, &filters),
+            vec![Colors::Red, Colors::Teal]
+        );
+        assert!(colors_in_use(&[], &[], &[]).is_empty());
+    }
+}
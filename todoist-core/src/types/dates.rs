@@ -0,0 +1,763 @@
+//! Due dates.
+//!
+//! `date` holds a `YYYY-MM-DD` string for full-day dates, or a
+//! `YYYY-MM-DDTHH:MM:SS` one for timed dates, which then also carry a
+//! `timezone`. `string` is the human readable form the user typed, e.g.
+//! `"every day"`.
+//!
+//! With the `chrono` feature, a few of the strings the apps understand can be
+//! turned into a due date:
+//!
+//! ```
+//! # #[cfg(feature = "chrono")]
+//! # {
+//! use chrono::NaiveDate;
+//! use todoist_core::types::dates::DueDate;
+//!
+//! let today = NaiveDate::from_ymd_opt(2024, 4, 30).unwrap();
+//! let due = DueDate::builder()
+//!     .from_string_on("Tomorrow", today)
+//!     .unwrap()
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(due.date(), "2024-05-01");
+//! # }
+//! ```
+use std::fmt;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::types::error::{self, BuildError, BuilderError, Resource};
+
+/// Format of the `date` of a full-day due date.
+#[cfg(feature = "chrono")]
+pub(crate) const FULL_DAY_FORMAT: &str = "%Y-%m-%d";
+/// Format of the `date` of a timed due date, without the `Z` suffix of dates
+/// fixed to UTC.
+#[cfg(feature = "chrono")]
+pub(crate) const TIMED_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// When a task is due. The API sends `null` for tasks without a due date,
+/// which is represented by a `DueDate` with `no_date` set.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct DueDate {
+    date: String,
+    timezone: Option<String>,
+    string: String,
+    lang: String,
+    #[serde(deserialize_with = "crate::types::flag")]
+    is_recurring: bool,
+    #[serde(skip)]
+    no_date: bool,
+}
+
+impl Default for DueDate {
+    fn default() -> Self {
+        DueDate {
+            date: String::new(),
+            timezone: None,
+            string: String::new(),
+            lang: String::from("en"),
+            is_recurring: false,
+            no_date: true,
+        }
+    }
+}
+
+impl DueDate {
+    pub fn builder() -> DueDateBuilder {
+        DueDateBuilder::default()
+    }
+
+    /// Assembles a due date field by field, bypassing the builder's checks,
+    /// for test harnesses that need arbitrary values.
+    #[cfg(feature = "test-util")]
+    pub fn from_parts(
+        date: &str,
+        string: &str,
+        lang: &str,
+        is_recurring: bool,
+        no_date: bool,
+    ) -> DueDate {
+        DueDate {
+            date: date.to_string(),
+            timezone: None,
+            string: string.to_string(),
+            lang: lang.to_string(),
+            is_recurring,
+            no_date,
+        }
+    }
+
+    pub fn date(&self) -> &str {
+        &self.date
+    }
+
+    pub fn timezone(&self) -> Option<&str> {
+        self.timezone.as_deref()
+    }
+
+    pub fn string(&self) -> &str {
+        &self.string
+    }
+
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    pub fn is_recurring(&self) -> bool {
+        self.is_recurring
+    }
+
+    /// Whether the task is due at a given time rather than some time during
+    /// the day. The API tells them apart by the `T` in the `date`.
+    pub fn is_timed(&self) -> bool {
+        self.date.contains('T')
+    }
+
+    /// Whether this stands for the absence of a due date.
+    pub fn no_date(&self) -> bool {
+        self.no_date
+    }
+
+    /// The day the task is due, `None` without a due date.
+    #[cfg(feature = "chrono")]
+    pub fn naive_date(&self) -> Option<NaiveDate> {
+        if self.no_date {
+            return None;
+        }
+        // Timed dates have the time appended, only the day matters here.
+        let day = self.date.get(..10)?;
+        NaiveDate::parse_from_str(day, FULL_DAY_FORMAT).ok()
+    }
+
+    /// The time a timed date is due at, read as is: dates fixed to UTC or to a
+    /// timezone are not converted to local time. `None` for full-day dates.
+    #[cfg(feature = "chrono")]
+    pub fn naive_datetime(&self) -> Option<NaiveDateTime> {
+        if self.no_date || !self.is_timed() {
+            return None;
+        }
+        let local = self.date.strip_suffix('Z').unwrap_or(&self.date);
+        NaiveDateTime::parse_from_str(local, TIMED_FORMAT).ok()
+    }
+
+    /// The same day, due at `hh:mm`. The string, recurrence and timezone are
+    /// kept, as is the `Z` of a date fixed to UTC.
+    #[cfg(feature = "chrono")]
+    pub fn with_time(&self, hh: u8, mm: u8) -> Result<DueDate, BuildError> {
+        if self.no_date {
+            return Err(BuildError::MissingDate);
+        }
+        let day = self
+            .naive_date()
+            .ok_or(BuildError::Invalid(BuilderError::InvalidDate("date")))?;
+        let time =
+            NaiveTime::from_hms_opt(hh.into(), mm.into(), 0).ok_or(BuildError::InvalidTime {
+                hour: hh,
+                minute: mm,
+            })?;
+        let utc = if self.date.ends_with('Z') { "Z" } else { "" };
+
+        Ok(DueDate {
+            date: format!("{}{}", day.and_time(time).format(TIMED_FORMAT), utc),
+            ..self.clone()
+        })
+    }
+
+    /// The same day, due some time during it. Full-day dates cannot have a
+    /// timezone, so it is dropped; the string and recurrence are kept.
+    #[cfg(feature = "chrono")]
+    pub fn without_time(&self) -> DueDate {
+        match self.naive_date() {
+            Some(day) => DueDate {
+                date: day.format(FULL_DAY_FORMAT).to_string(),
+                timezone: None,
+                ..self.clone()
+            },
+            None => self.clone(),
+        }
+    }
+}
+
+/// (De)serializes a `DueDate` as the nullable `due` field of the API.
+pub(crate) mod nullable {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(due: &DueDate, serializer: S) -> Result<S::Ok, S::Error> {
+        if due.no_date {
+            serializer.serialize_none()
+        } else {
+            serializer.serialize_some(due)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DueDate, D::Error> {
+        Ok(Option::<DueDate>::deserialize(deserializer)?.unwrap_or_default())
+    }
+}
+
+/// The human readable `string`, e.g. `every monday`, falling back to `date`
+/// when there is none, or `No date`.
+impl fmt::Display for DueDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.no_date {
+            f.write_str("No date")
+        } else if self.string.is_empty() {
+            f.write_str(&self.date)
+        } else {
+            f.write_str(&self.string)
+        }
+    }
+}
+
+/// The unit of a [`TaskDuration`].
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum DurationUnit {
+    Minute,
+    Day,
+}
+
+/// How long a task takes, e.g. to block time for it in a calendar.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TaskDuration {
+    amount: u32,
+    unit: DurationUnit,
+}
+
+impl TaskDuration {
+    pub fn new(amount: u32, unit: DurationUnit) -> Self {
+        TaskDuration { amount, unit }
+    }
+
+    pub fn amount(&self) -> u32 {
+        self.amount
+    }
+
+    pub fn unit(&self) -> DurationUnit {
+        self.unit
+    }
+
+    /// The duration in minutes, counting a day as 24 hours.
+    pub fn minutes(&self) -> u64 {
+        match self.unit {
+            DurationUnit::Minute => u64::from(self.amount),
+            DurationUnit::Day => u64::from(self.amount) * 24 * 60,
+        }
+    }
+}
+
+/// The fields of a due date, see [`DueDateBuilder::is_set`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DueDateField {
+    Date,
+    Timezone,
+    String,
+    Lang,
+    IsRecurring,
+}
+
+#[derive(Debug, Default)]
+pub struct DueDateBuilder {
+    date: Option<String>,
+    timezone: Option<String>,
+    string: Option<String>,
+    lang: Option<String>,
+    is_recurring: Option<bool>,
+}
+
+impl DueDateBuilder {
+    /// Sets the date, formatted as `YYYY-MM-DD`, or `YYYY-MM-DDTHH:MM:SS` for
+    /// a timed date. With the `chrono` feature, a date in neither format is
+    /// reported as invalid by `build`.
+    pub fn date(&mut self, value: &str) -> &mut Self {
+        self.date = Some(value.to_string());
+        self
+    }
+
+    /// Sets a date with a time of day, formatted as `YYYY-MM-DDTHH:MM:SS`.
+    /// Without a [`timezone`](DueDateBuilder::timezone) the date is floating,
+    /// due at that time wherever the user is.
+    pub fn datetime(&mut self, naive: &str) -> &mut Self {
+        self.date(naive)
+    }
+
+    /// Sets the timezone of a timed date, e.g. `"Europe/Paris"`, fixing it to
+    /// that timezone. Full-day dates cannot have one.
+    pub fn timezone(&mut self, value: &str) -> &mut Self {
+        self.timezone = Some(value.to_string());
+        self
+    }
+
+    pub fn string(&mut self, value: &str) -> &mut Self {
+        self.string = Some(value.to_string());
+        self
+    }
+
+    pub fn lang(&mut self, value: &str) -> &mut Self {
+        self.lang = Some(value.to_string());
+        self
+    }
+
+    pub fn is_recurring(&mut self, value: bool) -> &mut Self {
+        self.is_recurring = Some(value);
+        self
+    }
+
+    /// Sets the date to the first `day` strictly after `from`, so the next
+    /// Monday seen from a Monday is a week later, and the string to e.g.
+    /// `"next Monday"`.
+    #[cfg(feature = "chrono")]
+    pub fn next_weekday(&mut self, day: Weekday, from: NaiveDate) -> &mut Self {
+        let date = on_or_after(day, from + Days::new(1));
+        let name = match day {
+            Weekday::Mon => "Monday",
+            Weekday::Tue => "Tuesday",
+            Weekday::Wed => "Wednesday",
+            Weekday::Thu => "Thursday",
+            Weekday::Fri => "Friday",
+            Weekday::Sat => "Saturday",
+            Weekday::Sun => "Sunday",
+        };
+
+        self.date(&date.format(FULL_DAY_FORMAT).to_string())
+            .string(&format!("next {}", name))
+    }
+
+    /// Sets the date from what a user would type: an ISO `YYYY-MM-DD` date,
+    /// `today`, `tomorrow`, or one of the recurring `every day`, `daily` and
+    /// `every <weekday>`. The string is kept as typed.
+    ///
+    /// Anything else is an error, and leaves the builder unchanged.
+    #[cfg(feature = "chrono")]
+    pub fn from_string(&mut self, value: &str) -> Result<&mut Self, BuildError> {
+        self.from_string_on(value, Local::now().date_naive())
+    }
+
+    /// Like [`DueDateBuilder::from_string`], with relative dates resolved
+    /// against `today` instead of the current date.
+    #[cfg(feature = "chrono")]
+    pub fn from_string_on(
+        &mut self,
+        value: &str,
+        today: NaiveDate,
+    ) -> Result<&mut Self, BuildError> {
+        let value = value.trim();
+        let lowercase = value.to_lowercase();
+        let (date, recurring) = match lowercase.as_str() {
+            "today" => (today, false),
+            "tomorrow" => (today + Days::new(1), false),
+            "every day" | "daily" => (today, true),
+            other => {
+                if let Ok(date) = NaiveDate::parse_from_str(other, FULL_DAY_FORMAT) {
+                    (date, false)
+                } else if let Some(day) = other
+                    .strip_prefix("every ")
+                    .and_then(|day| day.parse::<Weekday>().ok())
+                {
+                    (on_or_after(day, today), true)
+                } else {
+                    return Err(BuildError::UnrecognizedDate);
+                }
+            }
+        };
+
+        Ok(self
+            .date(&date.format(FULL_DAY_FORMAT).to_string())
+            .string(value)
+            .is_recurring(recurring))
+    }
+
+    /// Whether `field` was given a value, as opposed to being left to its
+    /// default.
+    pub fn is_set(&self, field: DueDateField) -> bool {
+        match field {
+            DueDateField::Date => self.date.is_some(),
+            DueDateField::Timezone => self.timezone.is_some(),
+            DueDateField::String => self.string.is_some(),
+            DueDateField::Lang => self.lang.is_some(),
+            DueDateField::IsRecurring => self.is_recurring.is_some(),
+        }
+    }
+
+    /// Every problem with the fields set so far, given `date` as returned by
+    /// `normalize_date`.
+    fn validate(&self, date: Option<&Result<String, BuilderError>>) -> Vec<BuilderError> {
+        let mut errors = Vec::new();
+        match date {
+            None => errors.push(BuilderError::Missing("date")),
+            Some(Err(error)) => errors.push(error.clone()),
+            Some(Ok(date)) if self.timezone.is_some() && !date.contains('T') => {
+                errors.push(BuilderError::TimezoneWithoutTime)
+            }
+            Some(Ok(_)) => {}
+        }
+        errors
+    }
+
+    /// Like `build`, but reports every problem at once instead of stopping at
+    /// the first one.
+    pub fn build_checked(&self) -> Result<DueDate, Vec<BuilderError>> {
+        let date = self.date.as_deref().map(normalize_date);
+        let errors = self.validate(date.as_ref());
+        match date {
+            Some(Ok(date)) if errors.is_empty() => Ok(DueDate {
+                string: self.string.clone().unwrap_or_else(|| date.clone()),
+                date,
+                timezone: self.timezone.clone(),
+                lang: self.lang.clone().unwrap_or_else(|| String::from("en")),
+                is_recurring: self.is_recurring.unwrap_or(false),
+                no_date: false,
+            }),
+            _ => Err(errors),
+        }
+    }
+
+    pub fn build(&self) -> Result<DueDate, BuildError> {
+        self.build_checked()
+            .map_err(|errors| error::first(errors, Resource::DueDate))
+    }
+}
+
+/// Rewrites the dates and times chrono can read in the exact format the API
+/// uses for their kind, so full-day dates never contain a `T` and timed ones
+/// always do. Anything else is an invalid date.
+#[cfg(feature = "chrono")]
+fn normalize_date(date: &str) -> Result<String, BuilderError> {
+    if let Ok(day) = NaiveDate::parse_from_str(date, FULL_DAY_FORMAT) {
+        return Ok(day.format(FULL_DAY_FORMAT).to_string());
+    }
+    let (local, utc) = match date.strip_suffix('Z') {
+        Some(local) => (local, "Z"),
+        None => (date, ""),
+    };
+    let formats = [
+        TIMED_FORMAT,
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ];
+    formats
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(local, format).ok())
+        .map(|time| format!("{}{}", time.format(TIMED_FORMAT), utc))
+        .ok_or(BuilderError::InvalidDate("date"))
+}
+
+/// Without chrono, dates can't be read and are sent as given.
+#[cfg(not(feature = "chrono"))]
+fn normalize_date(date: &str) -> Result<String, BuilderError> {
+    Ok(date.to_string())
+}
+
+/// Words that mark a due date string as recurring, by the language code the
+/// API gives in `lang`. A word can belong to several languages.
+const RECURRENCE_WORDS: &[(&str, &[&str])] = &[
+    ("en", &["every", "daily", "weekly", "monthly", "yearly"]),
+    ("fr", &["chaque", "tous", "toutes", "quotidien"]),
+    ("de", &["jeden", "jede", "jedes", "täglich", "wöchentlich"]),
+    ("es", &["cada", "todos", "todas", "diario"]),
+    ("it", &["ogni", "giornaliero"]),
+    ("nl", &["elke", "iedere", "dagelijks"]),
+    ("pt", &["todo", "toda", "todos", "todas", "diariamente"]),
+];
+
+/// Whether the recurring due date `string` could be written in `lang`, to
+/// catch e.g. `"every day"` sent with `lang` `"fr"`, which the API would
+/// misread. Only obvious mismatches are flagged: strings with no recurrence
+/// word, and languages this crate knows no words of, always pass.
+pub fn validate_recurrence_lang(string: &str, lang: &str) -> bool {
+    let lang = lang
+        .split(['_', '-'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    if !RECURRENCE_WORDS.iter().any(|(code, _)| *code == lang) {
+        return true;
+    }
+
+    let string = string.to_lowercase();
+    let words: Vec<&str> = string.split(|c: char| !c.is_alphanumeric()).collect();
+    let mut languages = RECURRENCE_WORDS
+        .iter()
+        .filter(|(_, keywords)| keywords.iter().any(|keyword| words.contains(keyword)))
+        .map(|(code, _)| *code)
+        .peekable();
+    languages.peek().is_none() || languages.any(|code| code == lang)
+}
+
+/// Checks that `value`, the timestamp in `field`, is a `YYYY-MM-DD` date or an
+/// RFC 3339 date and time. Timestamps are only checked with the `chrono`
+/// feature.
+pub(crate) fn check_timestamp(
+    field: &'static str,
+    value: Option<&str>,
+) -> Result<(), BuilderError> {
+    #[cfg(feature = "chrono")]
+    if let Some(value) = value {
+        if NaiveDate::parse_from_str(value, FULL_DAY_FORMAT).is_err()
+            && DateTime::parse_from_rfc3339(value).is_err()
+        {
+            return Err(BuilderError::InvalidDate(field));
+        }
+    }
+    #[cfg(not(feature = "chrono"))]
+    let _ = (field, value);
+    Ok(())
+}
+
+/// The first `day` on or after `from`.
+#[cfg(feature = "chrono")]
+fn on_or_after(day: Weekday, from: NaiveDate) -> NaiveDate {
+    let ahead = (7 + day.num_days_from_monday() - from.weekday().num_days_from_monday()) % 7;
+    from + Days::new(u64::from(ahead))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn due_date_error_test() {
+        assert_eq!(DueDate::builder().build(), Err(BuildError::MissingDate));
+    }
+
+    #[test]
+    fn due_date_create_test() {
+        let due = DueDate::builder()
+            .date("2021-03-01")
+            .string("every month")
+            .is_recurring(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(due.date(), "2021-03-01");
+        assert_eq!(due.string(), "every month");
+        assert_eq!(due.lang(), "en");
+        assert!(due.is_recurring());
+        assert!(!due.no_date());
+        assert!(DueDate::default().no_date());
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn due_date_from_parts_test() {
+        let due = DueDate::from_parts("2021-03-01", "every monday", "fr", true, false);
+
+        assert_eq!(due.date(), "2021-03-01");
+        assert_eq!(due.string(), "every monday");
+        assert_eq!(due.lang(), "fr");
+        assert!(due.is_recurring());
+        assert!(!due.no_date());
+        assert!(DueDate::from_parts("", "", "en", false, true).no_date());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn due_date_next_weekday_test() {
+        // 2021-04-07 is a Wednesday.
+        let from = NaiveDate::from_ymd_opt(2021, 4, 7).unwrap();
+
+        let due = DueDate::builder()
+            .next_weekday(Weekday::Mon, from)
+            .build()
+            .unwrap();
+        assert_eq!(due.date(), "2021-04-12");
+        assert_eq!(due.naive_date().unwrap().weekday(), Weekday::Mon);
+        assert_eq!(due.string(), "next Monday");
+
+        let due = DueDate::builder()
+            .next_weekday(Weekday::Wed, from)
+            .build()
+            .unwrap();
+        assert_eq!(due.date(), "2021-04-14");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn due_date_from_string_test() {
+        let today = NaiveDate::from_ymd_opt(2024, 4, 30).unwrap();
+        let parse = |value: &str| {
+            DueDate::builder()
+                .from_string_on(value, today)
+                .map(|builder| builder.build().unwrap())
+        };
+
+        let due = parse("2024-05-01").unwrap();
+        assert_eq!(due.date(), "2024-05-01");
+        assert_eq!(due.string(), "2024-05-01");
+        assert!(!due.is_recurring());
+        assert!(!due.no_date());
+
+        assert_eq!(parse("Today").unwrap().date(), "2024-04-30");
+        let tomorrow = parse("Tomorrow").unwrap();
+        assert_eq!(tomorrow.date(), "2024-05-01");
+        assert_eq!(tomorrow.string(), "Tomorrow");
+
+        let recurring = parse("every day").unwrap();
+        assert!(recurring.is_recurring());
+        assert_eq!(recurring.date(), "2024-04-30");
+        // 2024-04-30 is a Tuesday.
+        assert_eq!(parse("Every Friday").unwrap().date(), "2024-05-03");
+
+        assert_eq!(parse("someday").unwrap_err(), BuildError::UnrecognizedDate);
+        assert!(DueDate::builder().from_string("today").is_ok());
+    }
+
+    #[test]
+    fn validate_recurrence_lang_test() {
+        assert!(validate_recurrence_lang("every day", "en"));
+        assert!(validate_recurrence_lang("Tous les jours", "fr"));
+        assert!(validate_recurrence_lang("todos los días", "pt_BR"));
+
+        assert!(!validate_recurrence_lang("every day", "fr"));
+        assert!(!validate_recurrence_lang("jeden Montag", "en"));
+
+        assert!(validate_recurrence_lang("tomorrow", "fr"));
+        assert!(validate_recurrence_lang("every day", "ja"));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn due_date_format_test() {
+        let build = |date: &str| DueDate::builder().date(date).build().unwrap();
+        let serialized = |due: &DueDate| serde_json::to_value(due).unwrap()["date"].clone();
+
+        for date in ["2024-05-01", "2024-5-1"].iter() {
+            let due = build(date);
+            assert!(!due.is_timed());
+            assert_eq!(serialized(&due), "2024-05-01");
+        }
+        for date in [
+            "2024-05-01T12:00:00",
+            "2024-05-01 12:00",
+            "2024-05-01T12:00",
+        ]
+        .iter()
+        {
+            let due = build(date);
+            assert!(due.is_timed());
+            assert_eq!(serialized(&due), "2024-05-01T12:00:00");
+        }
+        assert_eq!(build("2024-05-01 12:00Z").date(), "2024-05-01T12:00:00Z");
+
+        for date in ["not a date", "2024-05-01T12"].iter() {
+            let mut builder = DueDate::builder();
+            builder.date(date);
+            assert_eq!(
+                builder.build(),
+                Err(BuildError::Invalid(BuilderError::InvalidDate("date")))
+            );
+            assert_eq!(
+                builder.build_checked(),
+                Err(vec![BuilderError::InvalidDate("date")])
+            );
+        }
+    }
+
+    #[test]
+    fn due_date_datetime_test() {
+        let floating = DueDate::builder()
+            .datetime("2024-05-01T15:00:00")
+            .build()
+            .unwrap();
+        assert!(floating.is_timed());
+        assert_eq!(floating.date(), "2024-05-01T15:00:00");
+        assert_eq!(floating.timezone(), None);
+
+        let zoned = DueDate::builder()
+            .datetime("2024-05-01T15:00:00")
+            .timezone("America/New_York")
+            .build()
+            .unwrap();
+        assert!(zoned.is_timed());
+        assert_eq!(zoned.timezone(), Some("America/New_York"));
+        let json = serde_json::to_value(&zoned).unwrap();
+        assert_eq!(json["timezone"], "America/New_York");
+
+        let mut full_day = DueDate::builder();
+        full_day.date("2024-05-01").timezone("America/New_York");
+        assert_eq!(
+            full_day.build(),
+            Err(BuildError::Invalid(BuilderError::TimezoneWithoutTime))
+        );
+        assert_eq!(
+            full_day.build_checked(),
+            Err(vec![BuilderError::TimezoneWithoutTime])
+        );
+    }
+
+    #[test]
+    fn due_date_display_test() {
+        let due = DueDate::builder()
+            .date("2021-03-01")
+            .string("every monday")
+            .is_recurring(true)
+            .build()
+            .unwrap();
+        assert_eq!(due.to_string(), "every monday");
+
+        let due = DueDate::builder().date("2021-03-01").build().unwrap();
+        assert_eq!(due.to_string(), "2021-03-01");
+        assert_eq!(DueDate::default().to_string(), "No date");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn due_date_with_time_test() {
+        let daily = DueDate::builder()
+            .date("2024-05-01")
+            .string("every day")
+            .is_recurring(true)
+            .build()
+            .unwrap();
+
+        let timed = daily.with_time(15, 0).unwrap();
+        assert_eq!(timed.date(), "2024-05-01T15:00:00");
+        assert!(timed.is_timed());
+        assert!(timed.is_recurring());
+        assert_eq!(timed.string(), "every day");
+
+        let utc = DueDate::builder()
+            .date("2024-05-01T09:30:00Z")
+            .build()
+            .unwrap();
+        assert_eq!(utc.with_time(8, 5).unwrap().date(), "2024-05-01T08:05:00Z");
+
+        assert_eq!(
+            daily.with_time(24, 0),
+            Err(BuildError::InvalidTime {
+                hour: 24,
+                minute: 0
+            })
+        );
+        assert_eq!(
+            DueDate::default().with_time(9, 0),
+            Err(BuildError::MissingDate)
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn due_date_without_time_test() {
+        let zoned = DueDate::builder()
+            .datetime("2024-05-01T15:00:00")
+            .timezone("America/New_York")
+            .string("every day at 3pm")
+            .is_recurring(true)
+            .build()
+            .unwrap();
+
+        let full_day = zoned.without_time();
+        assert_eq!(full_day.date(), "2024-05-01");
+        assert!(!full_day.is_timed());
+        assert_eq!(full_day.timezone(), None);
+        assert!(full_day.is_recurring());
+        assert_eq!(full_day.without_time(), full_day);
+        assert_eq!(full_day.with_time(15, 0).unwrap().date(), zoned.date());
+        assert!(DueDate::default().without_time().no_date());
+    }
+}
@@ -0,0 +1,239 @@
+//! Errors reported by the builders.
+use std::error::Error;
+use std::fmt;
+
+/// The kind of resource a [`BuildError`] is about.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Resource {
+    Item,
+    Project,
+    Section,
+    Label,
+    Filter,
+    Note,
+    Reminder,
+    DueDate,
+}
+
+impl Resource {
+    fn name(self) -> &'static str {
+        match self {
+            Resource::Item => "Item",
+            Resource::Project => "Project",
+            Resource::Section => "Section",
+            Resource::Label => "Label",
+            Resource::Filter => "Filter",
+            Resource::Note => "Note",
+            Resource::Reminder => "Reminder",
+            Resource::DueDate => "Due date",
+        }
+    }
+
+    /// The lowercase name with its indefinite article, e.g. `"an item"`.
+    fn with_article(self) -> &'static str {
+        match self {
+            Resource::Item => "an item",
+            Resource::Project => "a project",
+            Resource::Section => "a section",
+            Resource::Label => "a label",
+            Resource::Filter => "a filter",
+            Resource::Note => "a note",
+            Resource::Reminder => "a reminder",
+            Resource::DueDate => "a due date",
+        }
+    }
+}
+
+/// Why a `build`, `to_builder` or other constructor could not produce a
+/// resource. Unlike `build_checked`, `build` only reports the first problem
+/// found.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum BuildError {
+    MissingUserId,
+    MissingProjectId(Resource),
+    MissingContent(Resource),
+    MissingName(Resource),
+    MissingQuery,
+    MissingDate,
+    MissingItemId,
+    /// A note is attached to either a task or a project.
+    MissingNoteParent,
+    /// A relative reminder needs to know how long before the due date to fire.
+    MissingMinuteOffset,
+    /// An absolute reminder needs to know when to fire.
+    MissingReminderDue,
+    MissingTitle,
+    MissingTimezone,
+    /// A field has a value the API doesn't accept, e.g. a name that is too
+    /// long.
+    Invalid(BuilderError),
+    /// Not a time of day, e.g. 24:00.
+    InvalidTime {
+        hour: u8,
+        minute: u8,
+    },
+    /// Only resources that came from the API, and have an id, can be updated.
+    NotSaved(Resource),
+    /// The string is none of the due date strings this crate understands.
+    UnrecognizedDate,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::MissingUserId => write!(f, "Item requires a user_id"),
+            BuildError::MissingProjectId(resource) => {
+                write!(f, "{} requires a project_id", resource.name())
+            }
+            BuildError::MissingContent(resource) => {
+                write!(f, "{} requires content", resource.name())
+            }
+            BuildError::MissingName(resource) => write!(f, "{} requires a name", resource.name()),
+            BuildError::MissingQuery => write!(f, "Filter requires a query"),
+            BuildError::MissingDate => write!(f, "Due date requires a date"),
+            BuildError::InvalidTime { hour, minute } => {
+                write!(f, "Invalid time of day {:02}:{:02}", hour, minute)
+            }
+            BuildError::MissingItemId => write!(f, "Reminder requires an item_id"),
+            BuildError::MissingNoteParent => {
+                write!(f, "Note requires an item_id or a project_id")
+            }
+            BuildError::MissingMinuteOffset => {
+                write!(f, "Relative reminder requires a minute_offset")
+            }
+            BuildError::MissingReminderDue => write!(f, "Absolute reminder requires a due date"),
+            BuildError::MissingTitle => write!(f, "Calendar event requires a title"),
+            BuildError::MissingTimezone => write!(f, "Calendar event requires a timezone"),
+            BuildError::Invalid(error) => error.fmt(f),
+            BuildError::NotSaved(resource) => {
+                write!(f, "Cannot update {} without an id", resource.with_article())
+            }
+            BuildError::UnrecognizedDate => write!(f, "Unrecognized due date string"),
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+/// A problem with the fields of a builder, as reported by `build_checked`, or
+/// with how a built resource relates to others.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum BuilderError {
+    /// A required field was never set.
+    Missing(&'static str),
+    /// A field is longer than the API accepts.
+    TooLong { field: &'static str, max: usize },
+    /// The section an item is in is not among the known sections.
+    UnknownSection(u64),
+    /// No label has this name.
+    UnknownLabel(String),
+    /// An enum like `Colors` or `Priority` was sent a number that is none of
+    /// its values.
+    UnknownDiscriminant { field: &'static str, value: u64 },
+    /// The section an item is in belongs to a different project than the item.
+    SectionProject { section_id: u64, project_id: u64 },
+    /// A full-day due date was given a timezone.
+    TimezoneWithoutTime,
+    /// A timestamp field is neither a `YYYY-MM-DD` date nor an RFC 3339 date
+    /// and time.
+    InvalidDate(&'static str),
+}
+
+impl fmt::Display for BuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuilderError::Missing(field) => write!(f, "Missing required field {}", field),
+            BuilderError::TooLong { field, max } => {
+                write!(f, "Field {} is longer than {} characters", field, max)
+            }
+            BuilderError::UnknownSection(id) => write!(f, "Unknown section {}", id),
+            BuilderError::UnknownLabel(name) => write!(f, "Unknown label {}", name),
+            BuilderError::UnknownDiscriminant { field, value } => {
+                write!(f, "Unknown {} {}", field, value)
+            }
+            BuilderError::SectionProject {
+                section_id,
+                project_id,
+            } => write!(
+                f,
+                "Section {} belongs to project {}, not the item's",
+                section_id, project_id
+            ),
+            BuilderError::TimezoneWithoutTime => {
+                write!(f, "Field timezone is only allowed on timed dates")
+            }
+            BuilderError::InvalidDate(field) => {
+                write!(f, "Field {} is not a YYYY-MM-DD or RFC 3339 date", field)
+            }
+        }
+    }
+}
+
+impl Error for BuilderError {}
+
+impl BuilderError {
+    /// The error `build` reports for this problem with a builder of
+    /// `resource`.
+    pub(crate) fn into_build_error(self, resource: Resource) -> BuildError {
+        match self {
+            BuilderError::Missing("user_id") => BuildError::MissingUserId,
+            BuilderError::Missing("project_id") => BuildError::MissingProjectId(resource),
+            BuilderError::Missing("content") => BuildError::MissingContent(resource),
+            BuilderError::Missing("name") => BuildError::MissingName(resource),
+            BuilderError::Missing("query") => BuildError::MissingQuery,
+            BuilderError::Missing("date") => BuildError::MissingDate,
+            BuilderError::Missing("item_id") => BuildError::MissingItemId,
+            BuilderError::Missing("item_id or project_id") => BuildError::MissingNoteParent,
+            BuilderError::Missing("minute_offset") => BuildError::MissingMinuteOffset,
+            BuilderError::Missing("due") => BuildError::MissingReminderDue,
+            error => BuildError::Invalid(error),
+        }
+    }
+}
+
+/// The first of the problems a failed `build_checked` reported, which is never
+/// empty, as `build` reports it.
+pub(crate) fn first(mut errors: Vec<BuilderError>, resource: Resource) -> BuildError {
+    errors.swap_remove(0).into_build_error(resource)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_error_display_test() {
+        assert_eq!(
+            BuildError::MissingProjectId(Resource::Section).to_string(),
+            "Section requires a project_id"
+        );
+        assert_eq!(
+            BuildError::NotSaved(Resource::Item).to_string(),
+            "Cannot update an item without an id"
+        );
+        assert_eq!(
+            BuildError::Invalid(BuilderError::TooLong {
+                field: "name",
+                max: 120,
+            })
+            .to_string(),
+            "Field name is longer than 120 characters"
+        );
+    }
+
+    #[test]
+    fn build_error_from_builder_error_test() {
+        assert_eq!(
+            BuilderError::Missing("name").into_build_error(Resource::Label),
+            BuildError::MissingName(Resource::Label)
+        );
+        assert_eq!(
+            BuilderError::Missing("item_id or project_id").into_build_error(Resource::Note),
+            BuildError::MissingNoteParent
+        );
+        assert_eq!(
+            BuilderError::InvalidDate("posted").into_build_error(Resource::Note),
+            BuildError::Invalid(BuilderError::InvalidDate("posted"))
+        );
+    }
+}
@@ -0,0 +1,805 @@
+//! Filters and the query language used to define them.
+//!
+//! Queries can also be evaluated locally with [`compile`] and [`evaluate`].
+//! Only a subset of the language is understood: the `today`, `tomorrow`,
+//! `overdue` (or `od`), `no date`, `recurring`, `p1` to `p4`, `@label` and
+//! `#Project` terms, combined with `&`, `|`, `!` and parentheses.
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "chrono")]
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::types::colors::Colors;
+use crate::types::error::{self, BuildError, BuilderError, Resource};
+use crate::types::favorite_unless_deleted;
+#[cfg(feature = "chrono")]
+use crate::types::items::Item;
+use crate::types::labels::LabelIndex;
+use crate::types::priority::Priority;
+use crate::types::projects::ProjectIndex;
+
+/// JSON keys of the API object that [`Filter`] deserializes into named fields,
+/// see [`super::unmodeled_fields`].
+pub(crate) const FIELDS: &[&str] = &[
+    "id",
+    "name",
+    "query",
+    "color",
+    "item_order",
+    "is_deleted",
+    "is_favorite",
+    "is_frozen",
+];
+
+/// A saved Todoist filter as returned by the Sync API.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct Filter {
+    id: Option<u64>,
+    name: String,
+    query: String,
+    color: Colors,
+    item_order: u32,
+    #[serde(deserialize_with = "crate::types::flag")]
+    is_deleted: bool,
+    #[serde(deserialize_with = "crate::types::flag")]
+    is_favorite: bool,
+    /// Set by the API on filters the user cannot edit.
+    #[serde(default, deserialize_with = "crate::types::flag")]
+    is_frozen: bool,
+}
+
+/// Commonly used filters, see [`Filter::preset`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FilterPreset {
+    Today,
+    Next7Days,
+    Priority1,
+    NoDate,
+}
+
+impl Filter {
+    pub fn builder() -> FilterBuilder {
+        FilterBuilder::default()
+    }
+
+    /// Starts an edit of an existing filter. Only filters that came from the
+    /// API, and therefore have an id, can be updated.
+    pub fn to_builder(&self) -> Result<FilterBuilder, BuildError> {
+        if self.id.is_none() {
+            return Err(BuildError::NotSaved(Resource::Filter));
+        }
+
+        Ok(FilterBuilder {
+            id: self.id,
+            name: Some(self.name.clone()),
+            query: Some(self.query.clone()),
+            color: Some(self.color),
+            item_order: Some(self.item_order),
+            is_deleted: Some(self.is_deleted),
+            is_favorite: Some(self.is_favorite),
+            is_frozen: Some(self.is_frozen),
+        })
+    }
+
+    /// Starts a new filter with the query and color of this one under
+    /// `new_name`, for saving a copy of it.
+    pub fn clone_as(&self, new_name: &str) -> FilterBuilder {
+        let mut builder = Filter::builder();
+        builder.name(new_name).query(&self.query).color(self.color);
+        builder
+    }
+
+    /// A new filter, not yet saved, for one of the common presets.
+    pub fn preset(preset: FilterPreset) -> Filter {
+        let (name, query, color) = match preset {
+            FilterPreset::Today => ("Today", String::from("today"), Colors::Green),
+            FilterPreset::Next7Days => ("Next 7 days", String::from("7 days"), Colors::Grape),
+            FilterPreset::Priority1 => (
+                "Priority 1",
+                QueryBuilder::new().priority(Priority::P1).build(),
+                Colors::Red,
+            ),
+            FilterPreset::NoDate => ("No date", String::from("no date"), Colors::Grey),
+        };
+
+        Filter::builder()
+            .name(name)
+            .query(&query)
+            .color(color)
+            .build()
+            .expect("presets set every required field")
+    }
+
+    pub fn id(&self) -> Option<u64> {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn color(&self) -> Colors {
+        self.color
+    }
+
+    pub fn item_order(&self) -> u32 {
+        self.item_order
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.is_deleted
+    }
+
+    pub fn is_favorite(&self) -> bool {
+        self.is_favorite
+    }
+
+    /// Whether the filter is managed by Todoist rather than the user, and
+    /// should not be offered for editing. The API only says so through the
+    /// `is_frozen` flag; there is no reserved id range to go by.
+    pub fn is_system(&self) -> bool {
+        self.is_frozen
+    }
+
+    /// Link opening the web app on the results of the filter's query, e.g.
+    /// `https://todoist.com/app?query=p1%20%7C%20today`.
+    pub fn to_query_url(&self) -> String {
+        let mut url = String::from("https://todoist.com/app?query=");
+        for byte in self.query.bytes() {
+            if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+                url.push(char::from(byte));
+            } else {
+                url.push_str(&format!("%{:02X}", byte));
+            }
+        }
+        url
+    }
+}
+
+/// The fields of a filter, see [`FilterBuilder::is_set`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FilterField {
+    Id,
+    Name,
+    Query,
+    Color,
+    ItemOrder,
+    IsDeleted,
+    IsFavorite,
+    IsFrozen,
+}
+
+#[derive(Debug, Default)]
+pub struct FilterBuilder {
+    id: Option<u64>,
+    name: Option<String>,
+    query: Option<String>,
+    color: Option<Colors>,
+    item_order: Option<u32>,
+    is_deleted: Option<bool>,
+    is_favorite: Option<bool>,
+    is_frozen: Option<bool>,
+}
+
+impl FilterBuilder {
+    pub fn id(&mut self, value: u64) -> &mut Self {
+        self.id = Some(value);
+        self
+    }
+
+    /// Leading and trailing whitespace is dropped.
+    pub fn name(&mut self, value: &str) -> &mut Self {
+        self.name = Some(value.trim().to_string());
+        self
+    }
+
+    pub fn query(&mut self, value: &str) -> &mut Self {
+        self.query = Some(value.to_string());
+        self
+    }
+
+    pub fn color(&mut self, value: Colors) -> &mut Self {
+        self.color = Some(value);
+        self
+    }
+
+    pub fn item_order(&mut self, value: u32) -> &mut Self {
+        self.item_order = Some(value);
+        self
+    }
+
+    pub fn is_deleted(&mut self, value: bool) -> &mut Self {
+        self.is_deleted = Some(value);
+        self
+    }
+
+    pub fn is_favorite(&mut self, value: bool) -> &mut Self {
+        self.is_favorite = Some(value);
+        self
+    }
+
+    pub fn is_frozen(&mut self, value: bool) -> &mut Self {
+        self.is_frozen = Some(value);
+        self
+    }
+
+    /// Whether `field` was given a value, as opposed to being left to its
+    /// default.
+    pub fn is_set(&self, field: FilterField) -> bool {
+        match field {
+            FilterField::Id => self.id.is_some(),
+            FilterField::Name => self.name.is_some(),
+            FilterField::Query => self.query.is_some(),
+            FilterField::Color => self.color.is_some(),
+            FilterField::ItemOrder => self.item_order.is_some(),
+            FilterField::IsDeleted => self.is_deleted.is_some(),
+            FilterField::IsFavorite => self.is_favorite.is_some(),
+            FilterField::IsFrozen => self.is_frozen.is_some(),
+        }
+    }
+
+    /// Every problem with the fields set so far, in field order.
+    fn validate(&self) -> Vec<BuilderError> {
+        let mut errors = Vec::new();
+        if self.name.is_none() {
+            errors.push(BuilderError::Missing("name"));
+        }
+        if self.query.is_none() {
+            errors.push(BuilderError::Missing("query"));
+        }
+        errors
+    }
+
+    /// Like `build`, but reports every problem at once instead of stopping at
+    /// the first one.
+    pub fn build_checked(&self) -> Result<Filter, Vec<BuilderError>> {
+        let errors = self.validate();
+        let (name, query) = match (&self.name, &self.query) {
+            (Some(name), Some(query)) if errors.is_empty() => (name.clone(), query.clone()),
+            _ => return Err(errors),
+        };
+        let is_deleted = self.is_deleted.unwrap_or(false);
+
+        Ok(Filter {
+            id: self.id,
+            name,
+            query,
+            color: self.color.unwrap_or_default(),
+            item_order: self.item_order.unwrap_or_default(),
+            is_deleted,
+            is_favorite: favorite_unless_deleted(
+                "filter",
+                self.is_favorite.unwrap_or(false),
+                is_deleted,
+            ),
+            is_frozen: self.is_frozen.unwrap_or(false),
+        })
+    }
+
+    pub fn build(&self) -> Result<Filter, BuildError> {
+        self.build_checked()
+            .map_err(|errors| error::first(errors, Resource::Filter))
+    }
+}
+
+/// Builds a Todoist filter query out of individual terms.
+///
+/// Terms are joined with `&`, so every term has to match:
+///
+/// ```
+/// use todoist_core::types::filters::QueryBuilder;
+/// use todoist_core::types::priority::Priority;
+///
+/// let query = QueryBuilder::new()
+///     .priority(Priority::P1)
+///     .term("today")
+///     .build();
+/// assert_eq!(query, "p1 & today");
+/// ```
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct QueryBuilder {
+    terms: Vec<String>,
+}
+
+impl QueryBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a raw query term such as `today` or `#Work`.
+    pub fn term(&mut self, term: &str) -> &mut Self {
+        self.terms.push(term.to_string());
+        self
+    }
+
+    /// Adds a priority term, using the same numbering as the apps.
+    pub fn priority(&mut self, priority: Priority) -> &mut Self {
+        let token = match priority {
+            Priority::P1 => "p1",
+            Priority::P2 => "p2",
+            Priority::P3 => "p3",
+            Priority::P4 => "p4",
+        };
+        self.term(token)
+    }
+
+    pub fn build(&self) -> String {
+        self.terms.join(" & ")
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FilterError {
+    /// Unbalanced parentheses or a missing operand.
+    Syntax(String),
+    /// A term this crate cannot evaluate.
+    UnknownTerm(String),
+    /// An `@label` term naming a label that does not exist.
+    UnknownLabel(String),
+    /// A `#Project` term naming a project that does not exist.
+    UnknownProject(String),
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FilterError::Syntax(query) => write!(f, "Invalid filter query: {}", query),
+            FilterError::UnknownTerm(term) => write!(f, "Unsupported filter term: {}", term),
+            FilterError::UnknownLabel(name) => write!(f, "Unknown label: {}", name),
+            FilterError::UnknownProject(name) => write!(f, "Unknown project: {}", name),
+        }
+    }
+}
+
+impl Error for FilterError {}
+
+/// A single condition of a query.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Term {
+    Today,
+    Tomorrow,
+    Overdue,
+    NoDate,
+    Recurring,
+    Priority(Priority),
+    Label(u64),
+    /// Items directly in the project, not in its subprojects.
+    Project(u64),
+}
+
+/// A query compiled with [`compile`], with label and project names resolved to
+/// ids.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Query {
+    Term(Term),
+    Not(Box<Query>),
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Word(String),
+}
+
+fn tokenize(query: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let flush = |word: &mut String, tokens: &mut Vec<Token>| {
+        let trimmed = word.trim();
+        if !trimmed.is_empty() {
+            tokens.push(Token::Word(trimmed.to_string()));
+        }
+        word.clear();
+    };
+
+    for c in query.chars() {
+        let token = match c {
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '&' => Token::And,
+            '|' => Token::Or,
+            '!' => Token::Not,
+            _ => {
+                word.push(c);
+                continue;
+            }
+        };
+        flush(&mut word, &mut tokens);
+        tokens.push(token);
+    }
+    flush(&mut word, &mut tokens);
+    tokens
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    position: usize,
+    query: &'a str,
+    labels: &'a LabelIndex,
+    projects: &'a ProjectIndex,
+}
+
+impl Parser<'_> {
+    fn syntax_error(&self) -> FilterError {
+        FilterError::Syntax(self.query.to_string())
+    }
+
+    fn next_is(&self, token: &Token) -> bool {
+        self.tokens.get(self.position) == Some(token)
+    }
+
+    fn or(&mut self) -> Result<Query, FilterError> {
+        let mut query = self.and()?;
+        while self.next_is(&Token::Or) {
+            self.position += 1;
+            query = Query::Or(Box::new(query), Box::new(self.and()?));
+        }
+        Ok(query)
+    }
+
+    fn and(&mut self) -> Result<Query, FilterError> {
+        let mut query = self.unary()?;
+        while self.next_is(&Token::And) {
+            self.position += 1;
+            query = Query::And(Box::new(query), Box::new(self.unary()?));
+        }
+        Ok(query)
+    }
+
+    fn unary(&mut self) -> Result<Query, FilterError> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        match token {
+            Some(Token::Not) => Ok(Query::Not(Box::new(self.unary()?))),
+            Some(Token::Open) => {
+                let query = self.or()?;
+                if !self.next_is(&Token::Close) {
+                    return Err(self.syntax_error());
+                }
+                self.position += 1;
+                Ok(query)
+            }
+            Some(Token::Word(word)) => {
+                let word = word.clone();
+                self.term(&word).map(Query::Term)
+            }
+            _ => Err(self.syntax_error()),
+        }
+    }
+
+    fn term(&self, word: &str) -> Result<Term, FilterError> {
+        if let Some(name) = word.strip_prefix('@') {
+            return self
+                .labels
+                .id_of(name)
+                .map(Term::Label)
+                .ok_or_else(|| FilterError::UnknownLabel(name.to_string()));
+        }
+        if let Some(name) = word.strip_prefix('#') {
+            return self
+                .projects
+                .id_of(name)
+                .map(Term::Project)
+                .ok_or_else(|| FilterError::UnknownProject(name.to_string()));
+        }
+
+        match word.to_lowercase().as_str() {
+            "today" => Ok(Term::Today),
+            "tomorrow" => Ok(Term::Tomorrow),
+            "overdue" | "od" => Ok(Term::Overdue),
+            "no date" => Ok(Term::NoDate),
+            "recurring" => Ok(Term::Recurring),
+            "p1" => Ok(Term::Priority(Priority::P1)),
+            "p2" => Ok(Term::Priority(Priority::P2)),
+            "p3" => Ok(Term::Priority(Priority::P3)),
+            "p4" => Ok(Term::Priority(Priority::P4)),
+            _ => Err(FilterError::UnknownTerm(word.to_string())),
+        }
+    }
+}
+
+/// Parses a filter query, resolving `@label` terms through `labels` and
+/// `#Project` terms through `projects`.
+pub fn compile(
+    query: &str,
+    labels: &LabelIndex,
+    projects: &ProjectIndex,
+) -> Result<Query, FilterError> {
+    let mut parser = Parser {
+        tokens: tokenize(query),
+        position: 0,
+        query,
+        labels,
+        projects,
+    };
+    let compiled = parser.or()?;
+    if parser.position != parser.tokens.len() {
+        return Err(parser.syntax_error());
+    }
+    Ok(compiled)
+}
+
+/// Whether `item` matches the compiled `query` on the day `today`.
+///
+/// `recurring_overdue` decides whether recurring tasks whose date has passed
+/// count as `overdue`; the Todoist apps do count them.
+#[cfg(feature = "chrono")]
+pub fn evaluate(query: &Query, item: &Item, today: NaiveDate, recurring_overdue: bool) -> bool {
+    let due = item.due();
+    let date = due.naive_date();
+
+    match query {
+        Query::Term(term) => match term {
+            Term::Today => date == Some(today),
+            Term::Tomorrow => date == today.succ_opt(),
+            Term::Overdue => {
+                date.is_some_and(|date| date < today) && (recurring_overdue || !due.is_recurring())
+            }
+            Term::NoDate => due.no_date(),
+            Term::Recurring => due.is_recurring(),
+            Term::Priority(priority) => item.priority() == priority,
+            Term::Label(id) => item.labels().contains(id),
+            Term::Project(id) => item.project_id() == *id,
+        },
+        Query::Not(query) => !evaluate(query, item, today, recurring_overdue),
+        Query::And(left, right) => {
+            evaluate(left, item, today, recurring_overdue)
+                && evaluate(right, item, today, recurring_overdue)
+        }
+        Query::Or(left, right) => {
+            evaluate(left, item, today, recurring_overdue)
+                || evaluate(right, item, today, recurring_overdue)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_error_test() {
+        assert_eq!(
+            Filter::builder().build(),
+            Err(BuildError::MissingName(Resource::Filter))
+        );
+        assert_eq!(
+            Filter::builder().name("Urgent").build(),
+            Err(BuildError::MissingQuery)
+        );
+    }
+
+    #[test]
+    fn filter_create_test() {
+        let filter = Filter::builder()
+            .name("Urgent")
+            .query("p1 & today")
+            .color(Colors::Red)
+            .build()
+            .unwrap();
+
+        assert_eq!(filter.name(), "Urgent");
+        assert_eq!(filter.query(), "p1 & today");
+        assert_eq!(filter.color(), Colors::Red);
+    }
+
+    #[test]
+    fn filter_deleted_favorite_test() {
+        let filter = Filter::builder()
+            .name("Old")
+            .query("today")
+            .is_favorite(true)
+            .is_deleted(true)
+            .build()
+            .unwrap();
+
+        assert!(filter.is_deleted());
+        assert!(!filter.is_favorite());
+    }
+
+    #[test]
+    fn filter_preset_test() {
+        assert_eq!(Filter::preset(FilterPreset::Today).query(), "today");
+        assert_eq!(Filter::preset(FilterPreset::Next7Days).query(), "7 days");
+        assert_eq!(Filter::preset(FilterPreset::Priority1).query(), "p1");
+        assert_eq!(Filter::preset(FilterPreset::NoDate).query(), "no date");
+
+        let today = Filter::preset(FilterPreset::Today);
+        assert_eq!(today.name(), "Today");
+        assert_eq!(today.id(), None);
+    }
+
+    #[test]
+    fn query_priority_test() {
+        assert_eq!(QueryBuilder::new().priority(Priority::P1).build(), "p1");
+        assert_eq!(QueryBuilder::new().priority(Priority::P4).build(), "p4");
+    }
+
+    #[test]
+    fn compile_test() {
+        let labels = LabelIndex::new(&[crate::types::labels::Label::builder()
+            .id(5)
+            .name("work")
+            .build()
+            .unwrap()]);
+        let projects = ProjectIndex::new(&[crate::types::projects::Project::builder()
+            .id(8)
+            .name("Side Project")
+            .build()
+            .unwrap()]);
+
+        assert_eq!(
+            compile("(p1 | @work) & !no date", &labels, &projects),
+            Ok(Query::And(
+                Box::new(Query::Or(
+                    Box::new(Query::Term(Term::Priority(Priority::P1))),
+                    Box::new(Query::Term(Term::Label(5))),
+                )),
+                Box::new(Query::Not(Box::new(Query::Term(Term::NoDate)))),
+            ))
+        );
+        assert_eq!(
+            compile("@home", &labels, &projects),
+            Err(FilterError::UnknownLabel(String::from("home")))
+        );
+        assert_eq!(
+            compile("#side project & p1", &labels, &projects),
+            Ok(Query::And(
+                Box::new(Query::Term(Term::Project(8))),
+                Box::new(Query::Term(Term::Priority(Priority::P1))),
+            ))
+        );
+        assert_eq!(
+            compile("#Work", &labels, &projects),
+            Err(FilterError::UnknownProject(String::from("Work")))
+        );
+        assert_eq!(
+            compile("assigned to: me", &labels, &projects),
+            Err(FilterError::UnknownTerm(String::from("assigned to: me")))
+        );
+        assert_eq!(
+            compile("(today", &labels, &projects),
+            Err(FilterError::Syntax(String::from("(today")))
+        );
+        assert_eq!(
+            compile("today &", &labels, &projects),
+            Err(FilterError::Syntax(String::from("today &")))
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn evaluate_recurring_overdue_test() {
+        use crate::types::dates::DueDate;
+
+        let today = NaiveDate::from_ymd_opt(2021, 3, 2).unwrap();
+        let overdue = compile("overdue", &LabelIndex::default(), &ProjectIndex::default()).unwrap();
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("Task");
+
+        let one_off = builder
+            .due(DueDate::builder().date("2021-03-01").build().unwrap())
+            .build()
+            .unwrap();
+        let recurring = builder
+            .due(
+                DueDate::builder()
+                    .date("2021-03-01")
+                    .string("every day")
+                    .is_recurring(true)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        assert!(evaluate(&overdue, &one_off, today, true));
+        assert!(evaluate(&overdue, &one_off, today, false));
+        assert!(evaluate(&overdue, &recurring, today, true));
+        assert!(!evaluate(&overdue, &recurring, today, false));
+
+        let due_today = compile("today", &LabelIndex::default(), &ProjectIndex::default()).unwrap();
+        assert!(!evaluate(&due_today, &recurring, today, true));
+    }
+
+    #[test]
+    fn filter_build_checked_test() {
+        assert_eq!(
+            Filter::builder().build_checked(),
+            Err(vec![
+                BuilderError::Missing("name"),
+                BuilderError::Missing("query"),
+            ])
+        );
+    }
+
+    #[test]
+    fn filter_is_system_test() {
+        let system: Filter = serde_json::from_str(
+            r#"{"id": 1, "name": "Assigned to me", "query": "assigned to: me",
+                "color": 47, "item_order": 0, "is_deleted": false,
+                "is_favorite": false, "is_frozen": true}"#,
+        )
+        .unwrap();
+        let user = Filter::builder()
+            .name("Urgent")
+            .query("p1")
+            .build()
+            .unwrap();
+
+        assert!(system.is_system());
+        assert!(!user.is_system());
+    }
+
+    #[test]
+    fn filter_to_query_url_test() {
+        let filter = Filter::builder()
+            .name("Urgent")
+            .query("p1 | (today & @work)")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            filter.to_query_url(),
+            "https://todoist.com/app?query=p1%20%7C%20%28today%20%26%20%40work%29"
+        );
+    }
+
+    #[test]
+    fn filter_update_test() {
+        let filter = Filter::builder()
+            .id(1)
+            .name("Urgent")
+            .query("p1")
+            .build()
+            .unwrap();
+        let updated = filter
+            .to_builder()
+            .unwrap()
+            .query("p1 | p2")
+            .color(Colors::Orange)
+            .build()
+            .unwrap();
+
+        assert_eq!(updated.id(), Some(1));
+        assert_eq!(updated.name(), "Urgent");
+        assert_eq!(updated.query(), "p1 | p2");
+        assert_eq!(updated.color(), Colors::Orange);
+        assert_eq!(
+            Filter::preset(FilterPreset::Today)
+                .to_builder()
+                .unwrap_err(),
+            BuildError::NotSaved(Resource::Filter)
+        );
+    }
+
+    #[test]
+    fn filter_clone_as_test() {
+        let filter = Filter::builder()
+            .id(1)
+            .name("Urgent")
+            .query("p1")
+            .color(Colors::Red)
+            .build()
+            .unwrap();
+        let copy = filter.clone_as("Urgent at work").build().unwrap();
+
+        assert_eq!(copy.id(), None);
+        assert_eq!(copy.name(), "Urgent at work");
+        assert_eq!(copy.query(), "p1");
+        assert_eq!(copy.color(), Colors::Red);
+        assert_ne!(copy, filter);
+    }
+}
@@ -0,0 +1,2543 @@
+//! Items, which is what the API calls tasks.
+#[cfg(feature = "chrono")]
+use std::collections::BTreeMap;
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "chrono")]
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read};
+
+#[cfg(feature = "chrono")]
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tracing::warn;
+
+use crate::types::dates::{self, DueDate, TaskDuration};
+use crate::types::error::{self, BuildError, BuilderError, Resource};
+use crate::types::labels::{LabelIndex, LabelResolver};
+use crate::types::order::Ordered;
+use crate::types::priority::Priority;
+use crate::types::sections::Section;
+
+/// Longest item content the API accepts, in characters.
+pub const MAX_CONTENT_LENGTH: usize = 500;
+
+/// JSON keys of the API object that [`Item`] deserializes into named fields,
+/// see [`super::unmodeled_fields`].
+pub(crate) const FIELDS: &[&str] = &[
+    "id",
+    "user_id",
+    "project_id",
+    "content",
+    "description",
+    "due",
+    "priority",
+    "parent_id",
+    "child_order",
+    "day_order",
+    "section_id",
+    "labels",
+    "checked",
+    "is_deleted",
+    "date_added",
+    "date_completed",
+    "responsible_uid",
+    "assigned_by_uid",
+    "duration",
+];
+
+/// A Todoist task as returned by the Sync API.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct Item {
+    id: Option<u64>,
+    user_id: u64,
+    project_id: u64,
+    content: String,
+    description: String,
+    #[serde(with = "dates::nullable")]
+    due: DueDate,
+    priority: Priority,
+    parent_id: Option<u64>,
+    child_order: u32,
+    /// Position in the Today view, -1 when the item is not ordered there.
+    #[serde(default = "no_day_order")]
+    day_order: i32,
+    section_id: Option<u64>,
+    labels: Vec<u64>,
+    #[serde(deserialize_with = "crate::types::flag")]
+    checked: bool,
+    #[serde(deserialize_with = "crate::types::flag")]
+    is_deleted: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date_added: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date_completed: Option<String>,
+    /// The collaborator the item is assigned to, in a shared project.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    responsible_uid: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    assigned_by_uid: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    duration: Option<TaskDuration>,
+    /// Fields of the API object this crate does not model, kept so they are
+    /// sent back unchanged.
+    #[cfg(feature = "lossless")]
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+/// `labels` with repeated ids dropped, in the order they were first added.
+fn unique_labels(labels: &[u64]) -> Vec<u64> {
+    let mut seen = HashSet::with_capacity(labels.len());
+    labels
+        .iter()
+        .copied()
+        .filter(|label| seen.insert(*label))
+        .collect()
+}
+
+fn no_day_order() -> i32 {
+    -1
+}
+
+impl Item {
+    pub fn builder() -> ItemBuilder {
+        ItemBuilder::default()
+    }
+
+    /// Starts an edit of an existing item. Only items that came from the API,
+    /// and therefore have an id, can be updated.
+    pub fn to_builder(&self) -> Result<ItemBuilder, BuildError> {
+        if self.id.is_none() {
+            return Err(BuildError::NotSaved(Resource::Item));
+        }
+
+        Ok(self.as_builder())
+    }
+
+    /// A builder with every field of this item, whether it was saved or not.
+    pub(crate) fn as_builder(&self) -> ItemBuilder {
+        ItemBuilder {
+            id: self.id,
+            user_id: Some(self.user_id),
+            project_id: Some(self.project_id),
+            content: Some(self.content.clone()),
+            description: Some(self.description.clone()),
+            due: Some(self.due.clone()),
+            priority: Some(self.priority),
+            parent_id: self.parent_id,
+            child_order: Some(self.child_order),
+            day_order: Some(self.day_order),
+            section_id: Some(self.section_id),
+            labels: Some(self.labels.clone()),
+            checked: Some(self.checked),
+            is_deleted: Some(self.is_deleted),
+            date_added: self.date_added.clone(),
+            date_completed: self.date_completed.clone(),
+            responsible_uid: self.responsible_uid,
+            assigned_by_uid: self.assigned_by_uid,
+            duration: self.duration,
+            #[cfg(feature = "lossless")]
+            extra: self.extra.clone(),
+        }
+    }
+
+    /// Reads a task in the shape of the REST API v2, which names some fields
+    /// differently, e.g. `is_completed` for `checked`, and sends ids as
+    /// strings.
+    ///
+    /// REST tasks list their labels by name, so the item has none; they can
+    /// be set with [`ItemBuilder::labels_from_names`].
+    pub fn from_rest_v2(json: &Value) -> Result<Item, serde_json::Error> {
+        let field = |key: &str| json.get(key).cloned().unwrap_or(Value::Null);
+        let id = |key: &str| rest_id(key, json.get(key));
+        let item = json!({
+            "id": id("id")?,
+            "user_id": id("creator_id")?,
+            "project_id": id("project_id")?,
+            "content": field("content"),
+            "description": field("description"),
+            "due": rest_due(json.get("due")),
+            "priority": field("priority"),
+            "parent_id": id("parent_id")?,
+            "child_order": field("order"),
+            "section_id": id("section_id")?,
+            "labels": [],
+            "checked": field("is_completed"),
+            "is_deleted": false,
+            "date_added": field("created_at"),
+            "responsible_uid": id("assignee_id")?,
+            "assigned_by_uid": id("assigner_id")?,
+            "duration": field("duration"),
+        });
+        serde_json::from_value(item)
+    }
+
+    /// Overlays the keys present in `partial`, a JSON object in the format of
+    /// the API, onto this item, keeping the fields it does not mention. On an
+    /// error, e.g. a value of the wrong type, the item is left unchanged.
+    pub fn merge_from(&mut self, partial: &Value) -> Result<(), serde_json::Error> {
+        let mut json = serde_json::to_value(&*self)?;
+        if let (Value::Object(fields), Value::Object(changes)) = (&mut json, partial) {
+            for (key, value) in changes {
+                fields.insert(key.clone(), value.clone());
+            }
+        }
+        *self = serde_json::from_value(json)?;
+        Ok(())
+    }
+
+    /// Same as `==`, but cheaper on items with different ids: those are told
+    /// apart without comparing any other field.
+    pub fn likely_same(&self, other: &Item) -> bool {
+        match (self.id, other.id) {
+            (Some(id), Some(other_id)) if id != other_id => false,
+            _ => self == other,
+        }
+    }
+
+    /// A task for a calendar event, titled like the event and due when it
+    /// starts. `start` is in the timezone `tz`, e.g. `"Europe/Paris"`.
+    #[cfg(feature = "chrono")]
+    pub fn from_calendar_event(
+        user_id: u64,
+        project_id: u64,
+        title: &str,
+        start: NaiveDateTime,
+        tz: &str,
+    ) -> Result<Item, BuildError> {
+        if title.trim().is_empty() {
+            return Err(BuildError::MissingTitle);
+        }
+        if tz.trim().is_empty() {
+            return Err(BuildError::MissingTimezone);
+        }
+        let due = DueDate::builder()
+            .date(&start.format(dates::TIMED_FORMAT).to_string())
+            .timezone(tz)
+            .string(&start.format("%Y-%m-%d %H:%M").to_string())
+            .build()?;
+
+        Item::builder()
+            .user_id(user_id)
+            .project_id(project_id)
+            .content(title)
+            .due(due)
+            .build()
+    }
+
+    pub fn id(&self) -> Option<u64> {
+        self.id
+    }
+
+    pub fn user_id(&self) -> u64 {
+        self.user_id
+    }
+
+    pub fn project_id(&self) -> u64 {
+        self.project_id
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn section_id(&self) -> Option<u64> {
+        self.section_id
+    }
+
+    pub fn due(&self) -> &DueDate {
+        &self.due
+    }
+
+    /// Whether the item is due at a given time rather than some time during
+    /// the day, see [`DueDate::is_timed`]. False for items without a due date.
+    pub fn has_due_time(&self) -> bool {
+        !self.due.no_date() && self.due.is_timed()
+    }
+
+    pub fn priority(&self) -> &Priority {
+        &self.priority
+    }
+
+    pub fn parent_id(&self) -> Option<u64> {
+        self.parent_id
+    }
+
+    pub fn child_order(&self) -> u32 {
+        self.child_order
+    }
+
+    pub fn labels(&self) -> &[u64] {
+        &self.labels
+    }
+
+    pub fn day_order(&self) -> i32 {
+        self.day_order
+    }
+
+    /// Sync responses send day orders apart from the items, see
+    /// [`crate::store::Store::apply`].
+    pub(crate) fn set_day_order(&mut self, order: i32) {
+        self.day_order = order;
+    }
+
+    pub fn checked(&self) -> bool {
+        self.checked
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.is_deleted
+    }
+
+    /// When the item was created, as a `YYYY-MM-DD` date or an RFC 3339 date
+    /// and time.
+    pub fn date_added(&self) -> Option<&str> {
+        self.date_added.as_deref()
+    }
+
+    /// When the item was completed, in the same format as
+    /// [`Item::date_added`].
+    pub fn date_completed(&self) -> Option<&str> {
+        self.date_completed.as_deref()
+    }
+
+    /// Id of the collaborator the item is assigned to, only ever set on items
+    /// of shared projects.
+    pub fn responsible_uid(&self) -> Option<u64> {
+        self.responsible_uid
+    }
+
+    /// Id of the user who assigned the item to its responsible collaborator.
+    pub fn assigned_by_uid(&self) -> Option<u64> {
+        self.assigned_by_uid
+    }
+
+    pub fn duration(&self) -> Option<TaskDuration> {
+        self.duration
+    }
+
+    /// Fields of the API object this crate does not model.
+    #[cfg(feature = "lossless")]
+    pub fn extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+
+    /// Shifts the item's id and the ids it references by `offset`, see
+    /// [`crate::store::Store::merge`].
+    pub(crate) fn offset_ids(&mut self, offset: u64) {
+        let shift = |id: &mut u64| *id = id.wrapping_add(offset);
+        self.id.iter_mut().for_each(shift);
+        shift(&mut self.project_id);
+        self.parent_id.iter_mut().for_each(shift);
+        self.section_id.iter_mut().for_each(shift);
+        self.labels.iter_mut().for_each(shift);
+    }
+
+    /// Days from `today` until the item is due: negative when overdue, zero
+    /// when due today and `None` without a due date.
+    #[cfg(feature = "chrono")]
+    pub fn days_until_due(&self, today: NaiveDate) -> Option<i64> {
+        self.due
+            .naive_date()
+            .map(|date| date.signed_duration_since(today).num_days())
+    }
+
+    /// The fields needed to show the item in a list, borrowed from the item.
+    pub fn summary(&self) -> TaskSummary<'_> {
+        TaskSummary {
+            id: self.id,
+            content: &self.content,
+            description: &self.description,
+            due_string: if self.due.no_date() {
+                None
+            } else {
+                Some(self.due.string())
+            },
+            priority: self.priority,
+            checked: self.checked,
+        }
+    }
+}
+
+/// The plain text of the markdown in `text`: links and images are replaced by
+/// their text, and header, bold, italic, strikethrough and code markers are
+/// dropped. Markers only count in pairs around text, so a lone `*` or `~`, as
+/// in `2 * 3` or `~5 min`, is kept, and so are underscores inside words, like
+/// in `snake_case`.
+pub fn strip_markdown(text: &str) -> String {
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        let unmarked = line.trim_start_matches('#');
+        let line = if unmarked.len() < line.len() && unmarked.starts_with(' ') {
+            unmarked.trim_start()
+        } else {
+            line
+        };
+        lines.push(strip_inline_markdown(line));
+    }
+    lines.join("\n")
+}
+
+fn strip_inline_markdown(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut plain = String::with_capacity(line.len());
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '!' if chars.get(i + 1) == Some(&'[') => i += 1,
+            '[' => match link_end(&chars[i..]) {
+                Some((close, end)) => {
+                    let text: String = chars[i + 1..i + close].iter().collect();
+                    plain.push_str(&strip_inline_markdown(&text));
+                    i += end + 1;
+                }
+                None => {
+                    plain.push('[');
+                    i += 1;
+                }
+            },
+            '*' | '_' | '~' | '`' => {
+                match emphasis_end(&chars[i..], i > 0 && chars[i - 1].is_alphanumeric()) {
+                    Some((marker, end)) => {
+                        let text: String = chars[i + marker..i + end].iter().collect();
+                        if chars[i] == '`' {
+                            plain.push_str(&text);
+                        } else {
+                            plain.push_str(&strip_inline_markdown(&text));
+                        }
+                        i += end + marker;
+                    }
+                    None => {
+                        plain.push(chars[i]);
+                        i += 1;
+                    }
+                }
+            }
+            c => {
+                plain.push(c);
+                i += 1;
+            }
+        }
+    }
+    plain
+}
+
+/// The length of the marker and the position of its closing pair when `chars`
+/// starts with emphasized, struck out or code text, like `**bold**`. As in
+/// CommonMark, the text can't start or end with a space, and `_` can't open or
+/// close inside a word; `after_word` tells whether a letter or digit precedes
+/// `chars`.
+fn emphasis_end(chars: &[char], after_word: bool) -> Option<(usize, usize)> {
+    let marker = ["**", "__", "~~", "*", "_", "`"]
+        .iter()
+        .map(|marker| marker.chars().collect::<Vec<char>>())
+        .find(|marker| chars.starts_with(marker))?;
+    let len = marker.len();
+    let word = |c: Option<&char>| c.is_some_and(|c| c.is_alphanumeric());
+    if marker[0] == '_' && after_word {
+        return None;
+    }
+    if chars.get(len).is_none_or(|c| c.is_whitespace()) {
+        return None;
+    }
+    (len + 1..chars.len())
+        .find(|&end| {
+            chars[end..].starts_with(&marker)
+                && !chars[end - 1].is_whitespace()
+                && !(marker[0] == '_' && word(chars.get(end + len)))
+        })
+        .map(|end| (len, end))
+}
+
+/// Positions of the `]` and the closing `)` when `chars` starts with a
+/// `[text](url)` link.
+fn link_end(chars: &[char]) -> Option<(usize, usize)> {
+    let close = chars.iter().position(|c| *c == ']')?;
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let end = close + chars[close..].iter().position(|c| *c == ')')?;
+    Some((close, end))
+}
+
+/// The priority `item` is shown with when subtasks inherit the priority of
+/// their parents: its own, unless it is the default `P4`, in which case the
+/// nearest ancestor's priority that is not `P4`. Ancestors missing from
+/// `by_id` end the walk.
+pub fn effective_priority(item: &Item, by_id: &HashMap<u64, Item>) -> Priority {
+    let mut current = item;
+    // Bounded so a parent cycle in bad data cannot loop forever.
+    for _ in 0..=by_id.len() {
+        if current.priority != Priority::P4 {
+            return current.priority;
+        }
+        match current.parent_id.and_then(|id| by_id.get(&id)) {
+            Some(parent) => current = parent,
+            None => break,
+        }
+    }
+    Priority::P4
+}
+
+/// What happens to the subtasks of a deleted task, see [`reparent_on_delete`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ReparentMode {
+    /// The direct subtasks become top-level tasks, keeping their own subtasks.
+    PromoteToRoot,
+    /// The subtasks, and theirs, are deleted as well.
+    CascadeDelete,
+}
+
+/// Deals with the subtasks in `items` of the task with id `deleted_parent`
+/// according to `mode`. The parent itself is left alone; removing it is up to
+/// the caller.
+pub fn reparent_on_delete(items: &mut Vec<Item>, deleted_parent: u64, mode: ReparentMode) {
+    match mode {
+        ReparentMode::PromoteToRoot => {
+            for item in items.iter_mut() {
+                if item.parent_id == Some(deleted_parent) {
+                    item.parent_id = None;
+                }
+            }
+        }
+        ReparentMode::CascadeDelete => {
+            let mut deleted = vec![deleted_parent];
+            let mut next = 0;
+            while let Some(parent) = deleted.get(next).copied() {
+                for item in items.iter() {
+                    match item.id {
+                        Some(id) if item.parent_id == Some(parent) && !deleted.contains(&id) => {
+                            deleted.push(id)
+                        }
+                        _ => {}
+                    }
+                }
+                next += 1;
+            }
+            items.retain(|item| !item.parent_id.is_some_and(|id| deleted.contains(&id)));
+        }
+    }
+}
+
+/// Checks that the section `item` is in, if any, belongs to the item's
+/// project. The API rejects items placed in another project's section.
+pub fn validate_section_project(item: &Item, sections: &[Section]) -> Result<(), BuilderError> {
+    let section_id = match item.section_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+    let section = sections
+        .iter()
+        .find(|section| section.id() == Some(section_id))
+        .ok_or(BuilderError::UnknownSection(section_id))?;
+    if section.project_id() != item.project_id {
+        return Err(BuilderError::SectionProject {
+            section_id,
+            project_id: section.project_id(),
+        });
+    }
+    Ok(())
+}
+
+/// Compact view of an [`Item`] for list rendering.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TaskSummary<'a> {
+    pub id: Option<u64>,
+    pub content: &'a str,
+    /// The description as written, in markdown.
+    pub description: &'a str,
+    /// Human readable due date, `None` if the item has no due date.
+    pub due_string: Option<&'a str>,
+    pub priority: Priority,
+    pub checked: bool,
+}
+
+impl TaskSummary<'_> {
+    /// The description without its markdown, see [`strip_markdown`].
+    pub fn plain_description(&self) -> String {
+        strip_markdown(self.description)
+    }
+}
+
+impl Ordered for Item {
+    fn order(&self) -> u32 {
+        self.child_order
+    }
+
+    fn set_order(&mut self, order: u32) {
+        self.child_order = order;
+    }
+}
+
+/// A one-line summary for list views, e.g. `Call Bob [P1] (every monday)`.
+/// The due date is left out when there is none.
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} [{}]", self.content, self.priority)?;
+        if !self.due.no_date() {
+            write!(f, " ({})", self.due)?;
+        }
+        Ok(())
+    }
+}
+
+/// An id of the REST API v2, sent as a string, as the number the Sync API
+/// uses. A missing id is `null`.
+fn rest_id(key: &str, value: Option<&Value>) -> Result<Value, serde_json::Error> {
+    match value {
+        None | Some(Value::Null) => Ok(Value::Null),
+        Some(Value::Number(id)) => Ok(Value::Number(id.clone())),
+        Some(Value::String(id)) => id
+            .parse::<u64>()
+            .map(|id| json!(id))
+            .map_err(|_| serde::de::Error::custom(format!("invalid {} {:?}", key, id))),
+        Some(other) => Err(serde::de::Error::custom(format!(
+            "invalid {} {}",
+            key, other
+        ))),
+    }
+}
+
+/// A due date of the REST API v2 in the shape of the Sync API. The REST API
+/// puts the time of timed dates in a separate `datetime`, with fractional
+/// seconds the Sync API does not send.
+fn rest_due(due: Option<&Value>) -> Value {
+    let due = match due {
+        Some(Value::Object(due)) => due,
+        _ => return Value::Null,
+    };
+    let date = match due.get("datetime").and_then(Value::as_str) {
+        Some(datetime) => {
+            let (seconds, zone) = datetime.split_at(datetime.find('Z').unwrap_or(datetime.len()));
+            let seconds = seconds.split('.').next().unwrap_or(seconds);
+            json!(format!("{}{}", seconds, zone))
+        }
+        None => due.get("date").cloned().unwrap_or(Value::Null),
+    };
+    json!({
+        "date": date,
+        "timezone": due.get("timezone").cloned().unwrap_or(Value::Null),
+        "string": due.get("string").cloned().unwrap_or(Value::Null),
+        "lang": due.get("lang").cloned().unwrap_or_else(|| json!("en")),
+        "is_recurring": due.get("is_recurring").cloned().unwrap_or(json!(false)),
+    })
+}
+
+/// Unique label ids referenced by `items`, in ascending order.
+pub fn collect_labels(items: &[Item]) -> Vec<u64> {
+    let mut labels: Vec<u64> = items
+        .iter()
+        .flat_map(|item| item.labels.iter().copied())
+        .collect();
+    labels.sort_unstable();
+    labels.dedup();
+    labels
+}
+
+/// Groups of non-deleted items sharing the same content, ignoring surrounding
+/// whitespace, within the same project. Only groups of two or more items are
+/// returned, in the order their first item appears in `items`.
+pub fn find_duplicates(items: &[Item]) -> Vec<Vec<&Item>> {
+    let mut groups: Vec<Vec<&Item>> = Vec::new();
+    let mut index: HashMap<(u64, &str), usize> = HashMap::new();
+
+    for item in items.iter().filter(|item| !item.is_deleted) {
+        let key = (item.project_id, item.content.trim());
+        match index.get(&key) {
+            Some(group) => groups[*group].push(item),
+            None => {
+                index.insert(key, groups.len());
+                groups.push(vec![item]);
+            }
+        }
+    }
+
+    groups.retain(|group| group.len() > 1);
+    groups
+}
+
+/// Buckets the items by the day they are due, in calendar order. Items without
+/// a due date are left out.
+#[cfg(feature = "chrono")]
+pub fn group_by_due_date(items: &[Item]) -> BTreeMap<NaiveDate, Vec<&Item>> {
+    let mut groups: BTreeMap<NaiveDate, Vec<&Item>> = BTreeMap::new();
+    for item in items {
+        if let Some(date) = item.due.naive_date() {
+            groups.entry(date).or_default().push(item);
+        }
+    }
+    groups
+}
+
+/// Ids of the pairs of timed items due on the same day whose time slots
+/// overlap, the earlier item of each pair first. A slot starts at the due time
+/// and lasts the item's duration; without one, it is just the due time. Slots
+/// that run past midnight don't conflict with the next day's items.
+///
+/// Items without a due time, completed or deleted items, items without an id
+/// and items whose slot would end past the dates chrono can represent are
+/// ignored. Due times are compared as written, so items fixed to different
+/// timezones are not told apart.
+#[cfg(feature = "chrono")]
+pub fn time_conflicts(items: &[Item]) -> Vec<(u64, u64)> {
+    let mut slots: Vec<(NaiveDateTime, NaiveDateTime, u64)> = items
+        .iter()
+        .filter(|item| !item.checked && !item.is_deleted)
+        .filter_map(|item| {
+            let start = item.due.naive_datetime()?;
+            let minutes = item.duration.map_or(0, |duration| duration.minutes());
+            // Durations so long the end can't be represented are skipped.
+            let minutes = chrono::Duration::try_minutes(i64::try_from(minutes).ok()?)?;
+            let end = start.checked_add_signed(minutes)?;
+            Some((start, end, item.id?))
+        })
+        .collect();
+    slots.sort();
+
+    let mut conflicts = Vec::new();
+    for (index, (start, end, id)) in slots.iter().enumerate() {
+        for (other_start, _, other_id) in &slots[index + 1..] {
+            if other_start.date() != start.date() || (other_start >= end && other_start != start) {
+                break;
+            }
+            conflicts.push((*id, *other_id));
+        }
+    }
+    conflicts
+}
+
+/// A minimal iCalendar document with a `VTODO` for each item, for importing
+/// the items into calendar apps. Items without a due date have no `DUE`.
+#[cfg(feature = "chrono")]
+pub fn to_ics(items: &[Item]) -> String {
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        String::from("PRODID:-//todoist-cli-rs//todoist_core//EN"),
+    ];
+    for item in items {
+        lines.push(String::from("BEGIN:VTODO"));
+        if let Some(id) = item.id {
+            lines.push(format!("UID:{}@todoist.com", id));
+        }
+        lines.push(format!("SUMMARY:{}", ics_text(&item.content)));
+        if let Some(due) = ics_due(&item.due) {
+            lines.push(due);
+        }
+        let status = if item.checked {
+            "COMPLETED"
+        } else {
+            "NEEDS-ACTION"
+        };
+        lines.push(format!("STATUS:{}", status));
+        lines.push(String::from("END:VTODO"));
+    }
+    lines.push(String::from("END:VCALENDAR"));
+
+    let mut ics = lines.join("\r\n");
+    ics.push_str("\r\n");
+    ics
+}
+
+/// The `DUE` line for `due`: a date for full-day dates, a local time for
+/// floating ones, and a time in UTC or in the date's timezone otherwise.
+#[cfg(feature = "chrono")]
+fn ics_due(due: &DueDate) -> Option<String> {
+    if !due.is_timed() {
+        let day = due.naive_date()?;
+        return Some(format!("DUE;VALUE=DATE:{}", day.format("%Y%m%d")));
+    }
+    let (local, utc) = match due.date().strip_suffix('Z') {
+        Some(local) => (local, "Z"),
+        None => (due.date(), ""),
+    };
+    let time = NaiveDateTime::parse_from_str(local, dates::TIMED_FORMAT).ok()?;
+    let time = time.format("%Y%m%dT%H%M%S");
+    Some(match due.timezone() {
+        Some(tz) if utc.is_empty() => format!("DUE;TZID={}:{}", tz, time),
+        _ => format!("DUE:{}{}", time, utc),
+    })
+}
+
+/// Escapes `text` for an iCalendar text value.
+#[cfg(feature = "chrono")]
+fn ics_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Reads the items of a JSON array one at a time, so that huge exports, like
+/// years of completed items, never have to be held in memory all at once.
+/// Each item is deserialized by `serde_json` straight from the reader; only
+/// the brackets and commas around them are read here.
+///
+/// Iteration stops after the first error. Anything but whitespace after the
+/// closing `]` is an error.
+pub fn stream_from_reader<R: Read>(
+    reader: R,
+) -> impl Iterator<Item = Result<Item, serde_json::Error>> {
+    ItemStream {
+        reader: BufReader::new(reader),
+        started: false,
+        done: false,
+    }
+}
+
+struct ItemStream<R: Read> {
+    reader: BufReader<R>,
+    started: bool,
+    done: bool,
+}
+
+impl<R: Read> ItemStream<R> {
+    /// Skips whitespace and returns the byte after it, leaving it unread.
+    fn peek(&mut self) -> Result<Option<u8>, serde_json::Error> {
+        loop {
+            let buffer = match self.reader.fill_buf() {
+                Ok(buffer) => buffer,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(serde_json::Error::io(err)),
+            };
+            if buffer.is_empty() {
+                return Ok(None);
+            }
+            match buffer.iter().position(|byte| !byte.is_ascii_whitespace()) {
+                Some(at) => {
+                    let byte = buffer[at];
+                    self.reader.consume(at);
+                    return Ok(Some(byte));
+                }
+                None => {
+                    let len = buffer.len();
+                    self.reader.consume(len);
+                }
+            }
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> serde_json::Error {
+        serde::de::Error::custom(format!("expected {} in the item array", expected))
+    }
+
+    /// Reads the `]` closing the array and checks that nothing follows it.
+    fn finish(&mut self) -> Result<Option<Item>, serde_json::Error> {
+        self.reader.consume(1);
+        match self.peek()? {
+            None => Ok(None),
+            Some(_) => Err(self.unexpected("nothing after ']'")),
+        }
+    }
+
+    fn next_item(&mut self) -> Result<Option<Item>, serde_json::Error> {
+        let separator = self.peek()?;
+        if !self.started {
+            if separator != Some(b'[') {
+                return Err(self.unexpected("'['"));
+            }
+            self.started = true;
+            self.reader.consume(1);
+            if self.peek()? == Some(b']') {
+                return self.finish();
+            }
+        } else {
+            match separator {
+                Some(b',') => self.reader.consume(1),
+                Some(b']') => return self.finish(),
+                _ => return Err(self.unexpected("',' or ']'")),
+            }
+        }
+
+        // Items are objects, which serde_json reads up to their closing `}`
+        // and no further, so the separator after one is left in `reader`.
+        let mut deserializer = serde_json::Deserializer::from_reader(&mut self.reader);
+        Item::deserialize(&mut deserializer).map(Some)
+    }
+}
+
+impl<R: Read> Iterator for ItemStream<R> {
+    type Item = Result<Item, serde_json::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let next = self.next_item();
+        if !matches!(next, Ok(Some(_))) {
+            self.done = true;
+        }
+        next.transpose()
+    }
+}
+
+/// What [`ItemBuilder::complete`] does with the due date of a one-off task.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum DueOnCompletion {
+    #[default]
+    Keep,
+    Clear,
+}
+
+/// The fields of an item, see [`ItemBuilder::is_set`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ItemField {
+    Id,
+    UserId,
+    ProjectId,
+    Content,
+    Description,
+    Due,
+    Priority,
+    ParentId,
+    ChildOrder,
+    DayOrder,
+    SectionId,
+    Labels,
+    Checked,
+    IsDeleted,
+    DateAdded,
+    DateCompleted,
+    ResponsibleUid,
+    AssignedByUid,
+    Duration,
+}
+
+#[derive(Debug, Default)]
+pub struct ItemBuilder {
+    id: Option<u64>,
+    user_id: Option<u64>,
+    project_id: Option<u64>,
+    content: Option<String>,
+    description: Option<String>,
+    due: Option<DueDate>,
+    priority: Option<Priority>,
+    parent_id: Option<u64>,
+    child_order: Option<u32>,
+    day_order: Option<i32>,
+    /// `Some(None)` records an explicit move out of any section.
+    section_id: Option<Option<u64>>,
+    labels: Option<Vec<u64>>,
+    checked: Option<bool>,
+    date_added: Option<String>,
+    date_completed: Option<String>,
+    responsible_uid: Option<u64>,
+    assigned_by_uid: Option<u64>,
+    duration: Option<TaskDuration>,
+    is_deleted: Option<bool>,
+    #[cfg(feature = "lossless")]
+    extra: HashMap<String, Value>,
+}
+
+impl ItemBuilder {
+    pub fn id(&mut self, value: u64) -> &mut Self {
+        self.id = Some(value);
+        self
+    }
+
+    pub fn user_id(&mut self, value: u64) -> &mut Self {
+        self.user_id = Some(value);
+        self
+    }
+
+    pub fn project_id(&mut self, value: u64) -> &mut Self {
+        self.project_id = Some(value);
+        self
+    }
+
+    /// Leading and trailing whitespace is dropped.
+    pub fn content(&mut self, value: &str) -> &mut Self {
+        self.content = Some(value.trim().to_string());
+        self
+    }
+
+    /// Adds `extra` to the end of the content, after a space if content was
+    /// already set. Like with `content`, surrounding whitespace is dropped, and
+    /// a blank `extra` leaves the content as it is.
+    pub fn append_content(&mut self, extra: &str) -> &mut Self {
+        let extra = extra.trim();
+        if extra.is_empty() {
+            return self;
+        }
+        match self.content.as_mut() {
+            Some(content) if !content.is_empty() => {
+                content.push(' ');
+                content.push_str(extra);
+            }
+            _ => self.content = Some(extra.to_string()),
+        }
+        self
+    }
+
+    /// Adds a markdown link to the content, see `append_content`. Brackets and
+    /// backslashes in `text` are escaped, and parentheses and spaces in `url`
+    /// percent-encoded, so neither can end the link early.
+    pub fn content_link(&mut self, text: &str, url: &str) -> &mut Self {
+        let mut link = String::from("[");
+        for c in text.chars() {
+            if matches!(c, '[' | ']' | '\\') {
+                link.push('\\');
+            }
+            link.push(c);
+        }
+        link.push_str("](");
+        for c in url.chars() {
+            match c {
+                '(' => link.push_str("%28"),
+                ')' => link.push_str("%29"),
+                ' ' => link.push_str("%20"),
+                c => link.push(c),
+            }
+        }
+        link.push(')');
+        self.append_content(&link)
+    }
+
+    pub fn description(&mut self, value: &str) -> &mut Self {
+        self.description = Some(value.to_string());
+        self
+    }
+
+    /// Sets the description if `value` is `Some`, and leaves it alone
+    /// otherwise. The other `_opt` setters work the same way.
+    pub fn description_opt(&mut self, value: Option<&str>) -> &mut Self {
+        match value {
+            Some(value) => self.description(value),
+            None => self,
+        }
+    }
+
+    /// Reads the whole of `reader` into the description, e.g. from a markdown
+    /// file.
+    pub fn description_from_reader<R: Read>(&mut self, mut reader: R) -> io::Result<&mut Self> {
+        let mut description = String::new();
+        reader.read_to_string(&mut description)?;
+        self.description = Some(description);
+        Ok(self)
+    }
+
+    /// Changing the due date of a completed task is usually a mistake, so it
+    /// is logged as a warning when the builder is already marked as checked.
+    pub fn due(&mut self, value: DueDate) -> &mut Self {
+        if self.checked == Some(true) && self.due.as_ref() != Some(&value) {
+            warn!(id = ?self.id, "changing the due date of a completed item");
+        }
+        self.due = Some(value);
+        self
+    }
+
+    pub fn due_opt(&mut self, value: Option<DueDate>) -> &mut Self {
+        match value {
+            Some(value) => self.due(value),
+            None => self,
+        }
+    }
+
+    pub fn priority(&mut self, value: Priority) -> &mut Self {
+        self.priority = Some(value);
+        self
+    }
+
+    pub fn priority_opt(&mut self, value: Option<Priority>) -> &mut Self {
+        match value {
+            Some(value) => self.priority(value),
+            None => self,
+        }
+    }
+
+    pub fn parent_id(&mut self, value: u64) -> &mut Self {
+        self.parent_id = Some(value);
+        self
+    }
+
+    pub fn parent_id_opt(&mut self, value: Option<u64>) -> &mut Self {
+        match value {
+            Some(value) => self.parent_id(value),
+            None => self,
+        }
+    }
+
+    pub fn child_order(&mut self, value: u32) -> &mut Self {
+        self.child_order = Some(value);
+        self
+    }
+
+    pub fn day_order(&mut self, value: i32) -> &mut Self {
+        self.day_order = Some(value);
+        self
+    }
+
+    pub fn section_id(&mut self, value: u64) -> &mut Self {
+        self.section_id = Some(Some(value));
+        self
+    }
+
+    /// Unlike [`ItemBuilder::clear_section`], `None` does not move the item
+    /// out of its section.
+    pub fn section_id_opt(&mut self, value: Option<u64>) -> &mut Self {
+        match value {
+            Some(value) => self.section_id(value),
+            None => self,
+        }
+    }
+
+    /// Takes the item out of its section, sent as `"section_id": null`.
+    pub fn clear_section(&mut self) -> &mut Self {
+        self.section_id = Some(None);
+        self
+    }
+
+    /// Replaces the labels, dropping repeated ids.
+    pub fn set_labels<I: IntoIterator<Item = u64>>(&mut self, values: I) -> &mut Self {
+        let values = values.into_iter();
+        let mut labels = Vec::with_capacity(values.size_hint().0);
+        let mut seen = HashSet::with_capacity(values.size_hint().0);
+        labels.extend(values.filter(|label| seen.insert(*label)));
+        self.labels = Some(labels);
+        self
+    }
+
+    /// Replaces the labels with the ones named `names`, looked up in `index`.
+    /// A leading `@` is ignored, so names can be taken as typed.
+    ///
+    /// If any name is unknown, the builder is left unchanged and the unknown
+    /// names are returned.
+    pub fn labels_from_names(
+        &mut self,
+        names: &[&str],
+        index: &LabelIndex,
+    ) -> Result<&mut Self, Vec<String>> {
+        let mut ids = Vec::with_capacity(names.len());
+        let mut unknown = Vec::new();
+        for name in names {
+            match index.id_of(name.trim_start_matches('@')) {
+                Some(id) => ids.push(id),
+                None => unknown.push(name.to_string()),
+            }
+        }
+        if !unknown.is_empty() {
+            return Err(unknown);
+        }
+        Ok(self.set_labels(ids))
+    }
+
+    /// Adds a label in place, without copying the labels added so far.
+    /// Repeated ids are dropped once, when the item is built, so adding
+    /// labels one by one takes linear time and no allocation beyond the
+    /// growing list.
+    pub fn label_add(&mut self, value: u64) -> &mut Self {
+        self.labels.get_or_insert_with(Vec::new).push(value);
+        self
+    }
+
+    pub fn label_remove(&mut self, value: u64) -> &mut Self {
+        if let Some(labels) = self.labels.as_mut() {
+            labels.retain(|label| *label != value);
+        }
+        self
+    }
+
+    /// Like `label_add`, but takes the label's name, with or without its
+    /// leading `@`. Fails, leaving the builder unchanged, if no label has
+    /// that name.
+    pub fn label_add_name(
+        &mut self,
+        name: &str,
+        resolver: &LabelResolver,
+    ) -> Result<&mut Self, BuilderError> {
+        let id = Self::resolve_label(name, resolver)?;
+        Ok(self.label_add(id))
+    }
+
+    /// Like `label_remove`, but takes the label's name.
+    pub fn label_remove_name(
+        &mut self,
+        name: &str,
+        resolver: &LabelResolver,
+    ) -> Result<&mut Self, BuilderError> {
+        let id = Self::resolve_label(name, resolver)?;
+        Ok(self.label_remove(id))
+    }
+
+    fn resolve_label(name: &str, resolver: &LabelResolver) -> Result<u64, BuilderError> {
+        resolver
+            .id_of(name.trim_start_matches('@'))
+            .ok_or_else(|| BuilderError::UnknownLabel(name.to_string()))
+    }
+
+    pub fn checked(&mut self, value: bool) -> &mut Self {
+        self.checked = Some(value);
+        self
+    }
+
+    /// Marks the item as completed.
+    ///
+    /// `due` decides what happens to the due date of a one-off task; pass
+    /// `DueOnCompletion::default()` to keep it. The due date of a recurring
+    /// task is always kept since it still drives the next occurrence.
+    pub fn complete(&mut self, due: DueOnCompletion) -> &mut Self {
+        self.checked = Some(true);
+        let recurring = self.due.as_ref().is_some_and(DueDate::is_recurring);
+        if due == DueOnCompletion::Clear && !recurring {
+            self.due = Some(DueDate::default());
+        }
+        self
+    }
+
+    pub fn is_deleted(&mut self, value: bool) -> &mut Self {
+        self.is_deleted = Some(value);
+        self
+    }
+
+    /// Sets when the item was created, as a `YYYY-MM-DD` date or an RFC 3339
+    /// date and time.
+    pub fn date_added(&mut self, value: &str) -> &mut Self {
+        self.date_added = Some(value.to_string());
+        self
+    }
+
+    /// Sets when the item was completed, in the same format as
+    /// [`ItemBuilder::date_added`].
+    pub fn date_completed(&mut self, value: &str) -> &mut Self {
+        self.date_completed = Some(value.to_string());
+        self
+    }
+
+    /// Assigns the item to the collaborator with this user id.
+    pub fn responsible_uid(&mut self, value: u64) -> &mut Self {
+        self.responsible_uid = Some(value);
+        self
+    }
+
+    pub fn assigned_by_uid(&mut self, value: u64) -> &mut Self {
+        self.assigned_by_uid = Some(value);
+        self
+    }
+
+    pub fn duration(&mut self, value: TaskDuration) -> &mut Self {
+        self.duration = Some(value);
+        self
+    }
+
+    /// Arguments of an `item_update` command applying the fields set on this
+    /// builder to the item with the builder's id.
+    pub fn update_args(&self) -> Result<Value, BuildError> {
+        let id = self.id.ok_or(BuildError::NotSaved(Resource::Item))?;
+
+        let mut args = self.field_args();
+        args.insert(String::from("id"), json!(id));
+        Ok(Value::Object(args))
+    }
+
+    /// Arguments of an `item_add` command creating an item with the fields
+    /// set on this builder. Nothing is checked, see
+    /// [`crate::client::CommandBatch::validate`].
+    pub fn add_args(&self) -> Value {
+        let mut args = self.field_args();
+        if let Some(project_id) = self.project_id {
+            args.insert(String::from("project_id"), json!(project_id));
+        }
+        if let Some(parent_id) = self.parent_id {
+            args.insert(String::from("parent_id"), json!(parent_id));
+        }
+        Value::Object(args)
+    }
+
+    /// The editable fields set on this builder, as command arguments.
+    fn field_args(&self) -> Map<String, Value> {
+        let mut args = Map::new();
+        if let Some(content) = &self.content {
+            args.insert(String::from("content"), json!(content));
+        }
+        if let Some(description) = &self.description {
+            args.insert(String::from("description"), json!(description));
+        }
+        if let Some(due) = &self.due {
+            let due = if due.no_date() {
+                Value::Null
+            } else {
+                json!(due)
+            };
+            args.insert(String::from("due"), due);
+        }
+        if let Some(priority) = &self.priority {
+            args.insert(String::from("priority"), json!(priority));
+        }
+        if let Some(section_id) = &self.section_id {
+            args.insert(String::from("section_id"), json!(section_id));
+        }
+        if let Some(labels) = &self.labels {
+            args.insert(String::from("labels"), json!(unique_labels(labels)));
+        }
+        if let Some(responsible_uid) = self.responsible_uid {
+            args.insert(String::from("responsible_uid"), json!(responsible_uid));
+        }
+        if let Some(duration) = &self.duration {
+            args.insert(String::from("duration"), json!(duration));
+        }
+        args
+    }
+
+    /// Whether `field` was given a value, as opposed to being left to its
+    /// default.
+    pub fn is_set(&self, field: ItemField) -> bool {
+        match field {
+            ItemField::Id => self.id.is_some(),
+            ItemField::UserId => self.user_id.is_some(),
+            ItemField::ProjectId => self.project_id.is_some(),
+            ItemField::Content => self.content.is_some(),
+            ItemField::Description => self.description.is_some(),
+            ItemField::Due => self.due.is_some(),
+            ItemField::Priority => self.priority.is_some(),
+            ItemField::ParentId => self.parent_id.is_some(),
+            ItemField::ChildOrder => self.child_order.is_some(),
+            ItemField::DayOrder => self.day_order.is_some(),
+            ItemField::SectionId => self.section_id.is_some(),
+            ItemField::Labels => self.labels.is_some(),
+            ItemField::Checked => self.checked.is_some(),
+            ItemField::IsDeleted => self.is_deleted.is_some(),
+            ItemField::DateAdded => self.date_added.is_some(),
+            ItemField::DateCompleted => self.date_completed.is_some(),
+            ItemField::ResponsibleUid => self.responsible_uid.is_some(),
+            ItemField::AssignedByUid => self.assigned_by_uid.is_some(),
+            ItemField::Duration => self.duration.is_some(),
+        }
+    }
+
+    /// The timestamp fields, which `build` checks the format of.
+    fn timestamps(&self) -> [(&'static str, Option<&str>); 2] {
+        [
+            ("date_added", self.date_added.as_deref()),
+            ("date_completed", self.date_completed.as_deref()),
+        ]
+    }
+
+    /// Every problem with the fields set so far, in field order.
+    fn validate(&self) -> Vec<BuilderError> {
+        let mut errors = Vec::new();
+        if self.user_id.is_none() {
+            errors.push(BuilderError::Missing("user_id"));
+        }
+        if self.project_id.is_none() {
+            errors.push(BuilderError::Missing("project_id"));
+        }
+        match &self.content {
+            // `content` trims, so blank content is stored empty.
+            None => errors.push(BuilderError::Missing("content")),
+            Some(content) if content.is_empty() => errors.push(BuilderError::Missing("content")),
+            Some(content) if content.chars().count() > MAX_CONTENT_LENGTH => {
+                errors.push(BuilderError::TooLong {
+                    field: "content",
+                    max: MAX_CONTENT_LENGTH,
+                })
+            }
+            Some(_) => {}
+        }
+        for (field, value) in self.timestamps().iter() {
+            if let Err(error) = dates::check_timestamp(field, *value) {
+                errors.push(error);
+            }
+        }
+        errors
+    }
+
+    /// Like `build`, but reports every problem at once instead of stopping at
+    /// the first one.
+    pub fn build_checked(&self) -> Result<Item, Vec<BuilderError>> {
+        let errors = self.validate();
+        let (user_id, project_id, content) = match (self.user_id, self.project_id, &self.content) {
+            (Some(user_id), Some(project_id), Some(content)) if errors.is_empty() => {
+                (user_id, project_id, content.clone())
+            }
+            _ => return Err(errors),
+        };
+
+        Ok(Item {
+            id: self.id,
+            user_id,
+            project_id,
+            content,
+            description: self.description.clone().unwrap_or_default(),
+            due: self.due.clone().unwrap_or_default(),
+            priority: self.priority.unwrap_or_default(),
+            parent_id: self.parent_id,
+            child_order: self.child_order.unwrap_or_default(),
+            day_order: self.day_order.unwrap_or_else(no_day_order),
+            section_id: self.section_id.flatten(),
+            labels: self
+                .labels
+                .as_deref()
+                .map(unique_labels)
+                .unwrap_or_default(),
+            checked: self.checked.unwrap_or(false),
+            is_deleted: self.is_deleted.unwrap_or(false),
+            date_added: self.date_added.clone(),
+            date_completed: self.date_completed.clone(),
+            responsible_uid: self.responsible_uid,
+            assigned_by_uid: self.assigned_by_uid,
+            duration: self.duration,
+            #[cfg(feature = "lossless")]
+            extra: self.extra.clone(),
+        })
+    }
+
+    pub fn build(&self) -> Result<Item, BuildError> {
+        self.build_checked()
+            .map_err(|errors| error::first(errors, Resource::Item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::dates::DurationUnit;
+    use crate::types::labels::Label;
+
+    #[test]
+    fn item_error_test() {
+        assert_eq!(Item::builder().build(), Err(BuildError::MissingUserId));
+        assert_eq!(
+            Item::builder().user_id(1).build(),
+            Err(BuildError::MissingProjectId(Resource::Item))
+        );
+        assert_eq!(
+            Item::builder().user_id(1).project_id(2).build(),
+            Err(BuildError::MissingContent(Resource::Item))
+        );
+        let item = Item::builder()
+            .user_id(1)
+            .project_id(2)
+            .content("Buy milk")
+            .build()
+            .unwrap();
+        assert!(item.to_builder().is_err());
+    }
+
+    #[test]
+    fn item_create_test() {
+        let item = Item::builder()
+            .user_id(1)
+            .project_id(2)
+            .content("Buy milk")
+            .priority(Priority::P2)
+            .label_add(3)
+            .label_add(4)
+            .label_add(3)
+            .label_remove(4)
+            .build()
+            .unwrap();
+
+        assert_eq!(item.content, "Buy milk");
+        assert_eq!(item.priority, Priority::P2);
+        assert_eq!(item.labels, vec![3]);
+        assert_eq!(item.description, "");
+        assert!(!item.checked);
+    }
+
+    #[test]
+    fn item_content_trim_test() {
+        let item = Item::builder()
+            .user_id(1)
+            .project_id(2)
+            .content("  Foo  ")
+            .build()
+            .unwrap();
+        assert_eq!(item.content, "Foo");
+
+        let item = Item::builder()
+            .user_id(1)
+            .project_id(2)
+            .content("\tFirst line\nSecond line \n")
+            .build()
+            .unwrap();
+        assert_eq!(item.content, "First line\nSecond line");
+    }
+
+    #[test]
+    fn item_update_test() {
+        let item = Item::builder()
+            .id(10)
+            .user_id(1)
+            .project_id(2)
+            .content("Buy milk")
+            .build()
+            .unwrap();
+        let updated = item.to_builder().unwrap().checked(true).build().unwrap();
+
+        assert_eq!(updated.id, Some(10));
+        assert_eq!(updated.content, "Buy milk");
+        assert!(updated.checked);
+    }
+
+    #[test]
+    fn item_description_from_reader_test() {
+        let markdown = "# Notes\n\nSome *details*.\n";
+        let item = Item::builder()
+            .user_id(1)
+            .project_id(2)
+            .content("Write report")
+            .description_from_reader(markdown.as_bytes())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(item.description, markdown);
+    }
+
+    #[test]
+    fn collect_labels_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("Task");
+        let first = builder.label_add(7).label_add(3).build().unwrap();
+        let second = builder.label_add(5).label_remove(7).build().unwrap();
+        let third = builder.label_remove(3).label_remove(5).build().unwrap();
+
+        assert_eq!(collect_labels(&[first, second, third]), vec![3, 5, 7]);
+        assert_eq!(collect_labels(&[]), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn item_complete_test() {
+        let one_off = DueDate::builder().date("2021-03-01").build().unwrap();
+        let recurring = DueDate::builder()
+            .date("2021-03-01")
+            .string("every day")
+            .is_recurring(true)
+            .build()
+            .unwrap();
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("Task");
+
+        let kept = builder
+            .due(one_off.clone())
+            .complete(DueOnCompletion::default())
+            .build()
+            .unwrap();
+        assert!(kept.checked);
+        assert_eq!(kept.due, one_off);
+
+        let cleared = builder
+            .due(one_off)
+            .complete(DueOnCompletion::Clear)
+            .build()
+            .unwrap();
+        assert!(cleared.checked);
+        assert!(cleared.due.no_date());
+
+        let repeated = builder
+            .due(recurring.clone())
+            .complete(DueOnCompletion::Clear)
+            .build()
+            .unwrap();
+        assert!(repeated.checked);
+        assert_eq!(repeated.due, recurring);
+    }
+
+    #[test]
+    fn find_duplicates_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2);
+        let items = vec![
+            builder.content("Buy milk").build().unwrap(),
+            builder.content("Walk the dog").build().unwrap(),
+            builder.content(" Buy milk ").build().unwrap(),
+            builder.content("Buy milk").project_id(3).build().unwrap(),
+            builder
+                .content("Buy milk")
+                .project_id(2)
+                .is_deleted(true)
+                .build()
+                .unwrap(),
+        ];
+
+        let duplicates = find_duplicates(&items);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0], vec![&items[0], &items[2]]);
+    }
+
+    #[test]
+    fn item_clear_section_test() {
+        let mut builder = Item::builder();
+        builder
+            .id(10)
+            .user_id(1)
+            .project_id(2)
+            .content("Task")
+            .section_id(3);
+        assert_eq!(builder.build().unwrap().section_id, Some(3));
+
+        builder.clear_section();
+        assert_eq!(builder.build().unwrap().section_id, None);
+        let args = builder.update_args().unwrap();
+        assert_eq!(args["id"], json!(10));
+        assert_eq!(args["section_id"], Value::Null);
+
+        assert_eq!(
+            Item::builder().section_id(3).update_args(),
+            Err(BuildError::NotSaved(Resource::Item))
+        );
+    }
+
+    #[test]
+    fn item_content_link_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2);
+
+        builder.content_link("Docs", "https://example.com/docs");
+        assert_eq!(
+            builder.build().unwrap().content,
+            "[Docs](https://example.com/docs)"
+        );
+
+        builder
+            .content("Read")
+            .content_link("the docs", "https://example.com");
+        assert_eq!(
+            builder.build().unwrap().content,
+            "Read [the docs](https://example.com)"
+        );
+
+        builder.content("").content_link(
+            "Rust [lang]",
+            "https://en.wikipedia.org/wiki/Rust_(programming language)",
+        );
+        assert_eq!(
+            builder.build().unwrap().content,
+            r"[Rust \[lang\]](https://en.wikipedia.org/wiki/Rust_%28programming%20language%29)"
+        );
+    }
+
+    #[test]
+    fn item_append_content_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2);
+
+        builder.append_content("Call Bob ");
+        assert_eq!(builder.build().unwrap().content, "Call Bob");
+
+        builder.append_content("about the invoice");
+        assert_eq!(
+            builder.build().unwrap().content,
+            "Call Bob about the invoice"
+        );
+
+        builder.append_content("").append_content(" \t ");
+        assert_eq!(
+            builder.build().unwrap().content,
+            "Call Bob about the invoice"
+        );
+        assert!(!Item::builder()
+            .append_content("  ")
+            .is_set(ItemField::Content));
+    }
+
+    #[test]
+    fn item_blank_content_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("   ");
+        assert_eq!(
+            builder.build(),
+            Err(BuildError::MissingContent(Resource::Item))
+        );
+        assert_eq!(
+            builder.build_checked(),
+            Err(vec![BuilderError::Missing("content")])
+        );
+    }
+
+    #[test]
+    fn item_build_checked_test() {
+        assert_eq!(
+            Item::builder().build_checked(),
+            Err(vec![
+                BuilderError::Missing("user_id"),
+                BuilderError::Missing("project_id"),
+                BuilderError::Missing("content"),
+            ])
+        );
+        assert_eq!(
+            Item::builder().project_id(2).build_checked(),
+            Err(vec![
+                BuilderError::Missing("user_id"),
+                BuilderError::Missing("content"),
+            ])
+        );
+        let item = Item::builder()
+            .user_id(1)
+            .project_id(2)
+            .content("Task")
+            .build_checked()
+            .unwrap();
+        assert_eq!(item.content, "Task");
+    }
+
+    #[test]
+    fn item_summary_test() {
+        let mut builder = Item::builder();
+        builder
+            .id(10)
+            .user_id(1)
+            .project_id(2)
+            .content("Pay rent")
+            .priority(Priority::P1);
+
+        let undated = builder.build().unwrap();
+        assert_eq!(
+            undated.summary(),
+            TaskSummary {
+                id: Some(10),
+                content: "Pay rent",
+                description: "",
+                due_string: None,
+                priority: Priority::P1,
+                checked: false,
+            }
+        );
+
+        let dated = builder
+            .due(
+                DueDate::builder()
+                    .date("2021-03-01")
+                    .string("every 1st")
+                    .build()
+                    .unwrap(),
+            )
+            .checked(true)
+            .build()
+            .unwrap();
+        let summary = dated.summary();
+        assert_eq!(summary.due_string, Some("every 1st"));
+        assert!(summary.checked);
+    }
+
+    #[test]
+    fn stream_from_reader_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2);
+        let items = vec![
+            builder
+                .id(1)
+                .content("Say \"hi\" [to] {everyone}")
+                .build()
+                .unwrap(),
+            builder
+                .id(2)
+                .content("Second, with a comma")
+                .build()
+                .unwrap(),
+            builder.id(3).content("Third").build().unwrap(),
+        ];
+        let json = serde_json::to_vec_pretty(&items).unwrap();
+
+        let streamed: Vec<Item> = stream_from_reader(json.as_slice())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(streamed.len(), 3);
+        assert_eq!(streamed, items);
+
+        assert_eq!(stream_from_reader(&b" [ ] "[..]).count(), 0);
+        let mut broken = stream_from_reader(&b"{}"[..]);
+        assert!(broken.next().unwrap().is_err());
+        assert!(broken.next().is_none());
+
+        let mut trailing = json.clone();
+        trailing.extend_from_slice(b"\n]");
+        let mut streamed = stream_from_reader(trailing.as_slice());
+        assert_eq!(streamed.by_ref().take(3).filter(Result::is_ok).count(), 3);
+        assert!(streamed.next().unwrap().is_err());
+        assert!(streamed.next().is_none());
+        assert!(stream_from_reader(&b"[] x"[..]).next().unwrap().is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn item_days_until_due_test() {
+        let today = NaiveDate::from_ymd_opt(2021, 3, 10).unwrap();
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("Task");
+        let mut due_on = |date: &str| {
+            builder
+                .due(DueDate::builder().date(date).build().unwrap())
+                .build()
+                .unwrap()
+                .days_until_due(today)
+        };
+
+        assert_eq!(due_on("2021-03-07"), Some(-3));
+        assert_eq!(due_on("2021-03-10"), Some(0));
+        assert_eq!(due_on("2021-04-01"), Some(22));
+
+        let undated = Item::builder()
+            .user_id(1)
+            .project_id(2)
+            .content("Task")
+            .build()
+            .unwrap();
+        assert_eq!(undated.days_until_due(today), None);
+    }
+
+    #[cfg(feature = "lossless")]
+    #[test]
+    fn item_lossless_round_trip_test() {
+        let item: Item = serde_json::from_str(
+            r#"{"id": 7, "user_id": 1, "project_id": 2, "content": "Call Bob",
+                "description": "", "due": null, "priority": 1,
+                "parent_id": null, "child_order": 1, "section_id": null,
+                "labels": [], "checked": false, "is_deleted": false,
+                "sync_id": null, "added_by_uid": 1}"#,
+        )
+        .unwrap();
+
+        let edited = item
+            .to_builder()
+            .unwrap()
+            .content("Call Alice")
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&edited).unwrap();
+        assert_eq!(json["content"], json!("Call Alice"));
+        assert_eq!(json["added_by_uid"], json!(1));
+        assert_eq!(json["sync_id"], Value::Null);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn group_by_due_date_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("Task");
+        let mut due_on = |id: u64, date: &str| {
+            builder
+                .id(id)
+                .due(DueDate::builder().date(date).build().unwrap())
+                .build()
+                .unwrap()
+        };
+        let items = vec![
+            due_on(1, "2021-03-02"),
+            due_on(2, "2021-03-01"),
+            due_on(3, "2021-03-02"),
+            Item::builder()
+                .id(4)
+                .user_id(1)
+                .project_id(2)
+                .content("Someday")
+                .build()
+                .unwrap(),
+        ];
+
+        let groups = group_by_due_date(&items);
+        let days: Vec<_> = groups.keys().map(|day| day.to_string()).collect();
+        assert_eq!(days, vec!["2021-03-01", "2021-03-02"]);
+        let ids = |day: &str| -> Vec<Option<u64>> {
+            groups[&day.parse::<NaiveDate>().unwrap()]
+                .iter()
+                .map(|item| item.id)
+                .collect()
+        };
+        assert_eq!(ids("2021-03-01"), vec![Some(2)]);
+        assert_eq!(ids("2021-03-02"), vec![Some(1), Some(3)]);
+    }
+
+    #[test]
+    fn item_due_on_checked_warning_test() {
+        let due = DueDate::builder().date("2021-03-01").build().unwrap();
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("Task");
+
+        let logs = crate::capture_logs(|| {
+            builder.due(due.clone());
+        });
+        assert_eq!(logs, "");
+
+        builder.checked(true);
+        let logs = crate::capture_logs(|| {
+            builder.due(due.clone());
+        });
+        assert_eq!(logs, "");
+
+        let logs = crate::capture_logs(|| {
+            builder.due(DueDate::builder().date("2021-03-02").build().unwrap());
+        });
+        assert!(logs.contains("WARN"));
+        assert!(logs.contains("changing the due date of a completed item"));
+        assert_eq!(builder.build().unwrap().due.date(), "2021-03-02");
+    }
+
+    #[test]
+    fn validate_section_project_test() {
+        let sections = vec![
+            Section::builder()
+                .id(10)
+                .name("Backlog")
+                .project_id(1)
+                .build()
+                .unwrap(),
+            Section::builder()
+                .id(20)
+                .name("Done")
+                .project_id(2)
+                .build()
+                .unwrap(),
+        ];
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(1).content("Task");
+
+        assert_eq!(
+            validate_section_project(&builder.build().unwrap(), &sections),
+            Ok(())
+        );
+        assert_eq!(
+            validate_section_project(&builder.section_id(10).build().unwrap(), &sections),
+            Ok(())
+        );
+        assert_eq!(
+            validate_section_project(&builder.section_id(20).build().unwrap(), &sections),
+            Err(BuilderError::SectionProject {
+                section_id: 20,
+                project_id: 2,
+            })
+        );
+        assert_eq!(
+            validate_section_project(&builder.section_id(30).build().unwrap(), &sections),
+            Err(BuilderError::UnknownSection(30))
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn item_from_calendar_event_test() {
+        let start = NaiveDate::from_ymd_opt(2021, 4, 12)
+            .unwrap()
+            .and_hms_opt(14, 30, 0)
+            .unwrap();
+
+        let item = Item::from_calendar_event(1, 2, " Dentist ", start, "Europe/Paris").unwrap();
+        assert_eq!(item.content, "Dentist");
+        assert_eq!(item.project_id(), 2);
+        assert_eq!(item.due().date(), "2021-04-12T14:30:00");
+        assert_eq!(item.due().timezone(), Some("Europe/Paris"));
+        assert_eq!(item.due().string(), "2021-04-12 14:30");
+        assert_eq!(item.due().naive_date(), Some(start.date()));
+
+        assert_eq!(
+            Item::from_calendar_event(1, 2, "Dentist", start, ""),
+            Err(BuildError::MissingTimezone)
+        );
+    }
+
+    #[test]
+    fn item_many_labels_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("Task");
+        for label in 0..1000 {
+            builder.label_add(label).label_add(label);
+        }
+        let item = builder.build().unwrap();
+        assert_eq!(item.labels(), (0..1000).collect::<Vec<u64>>().as_slice());
+
+        builder.set_labels((0..1000).chain(0..1000).rev());
+        let item = builder.build().unwrap();
+        assert_eq!(item.labels().len(), 1000);
+        assert_eq!(item.labels()[0], 999);
+    }
+
+    #[test]
+    fn strip_markdown_test() {
+        assert_eq!(
+            strip_markdown("See [the docs](https://example.com) and ![logo](logo.png)"),
+            "See the docs and logo"
+        );
+        assert_eq!(
+            strip_markdown("**Bold**, *italic*, __also bold__ and ~~gone~~"),
+            "Bold, italic, also bold and gone"
+        );
+        assert_eq!(
+            strip_markdown("# Title\n### Sub title\n#hashtag"),
+            "Title\nSub title\n#hashtag"
+        );
+        assert_eq!(
+            strip_markdown("Call `snake_case` [sic] fn"),
+            "Call snake_case [sic] fn"
+        );
+        assert_eq!(strip_markdown("2 * 3 = 6"), "2 * 3 = 6");
+        assert_eq!(strip_markdown("2 * 3 * 4"), "2 * 3 * 4");
+        assert_eq!(strip_markdown("~5 min, ~ 1 h"), "~5 min, ~ 1 h");
+        assert_eq!(
+            strip_markdown("snake_case and other_name"),
+            "snake_case and other_name"
+        );
+        assert_eq!(strip_markdown("`a*b*` is _code_"), "a*b* is code");
+
+        let item = Item::builder()
+            .user_id(1)
+            .project_id(2)
+            .content("Read")
+            .description("**Chapter** [one](https://example.com)")
+            .build()
+            .unwrap();
+        let summary = item.summary();
+        assert_eq!(
+            summary.description,
+            "**Chapter** [one](https://example.com)"
+        );
+        assert_eq!(summary.plain_description(), "Chapter one");
+    }
+
+    #[test]
+    fn item_getters_test() {
+        let due = DueDate::builder().date("2021-04-12").build().unwrap();
+        let item = Item::builder()
+            .id(10)
+            .user_id(1)
+            .project_id(2)
+            .content("Pay rent")
+            .description("Before the 5th")
+            .due(due.clone())
+            .priority(Priority::P2)
+            .parent_id(9)
+            .child_order(3)
+            .section_id(4)
+            .label_add(5)
+            .checked(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(item.id(), Some(10));
+        assert_eq!(item.user_id(), 1);
+        assert_eq!(item.project_id(), 2);
+        assert_eq!(item.content(), "Pay rent");
+        assert_eq!(item.description(), "Before the 5th");
+        assert_eq!(item.due(), &due);
+        assert_eq!(item.priority(), &Priority::P2);
+        assert_eq!(item.parent_id(), Some(9));
+        assert_eq!(item.child_order(), 3);
+        assert_eq!(item.day_order(), -1);
+        assert_eq!(item.section_id(), Some(4));
+        assert_eq!(item.labels(), &[5]);
+        assert!(item.checked());
+        assert!(!item.is_deleted());
+    }
+
+    #[test]
+    fn item_opt_setters_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("Task");
+
+        let item = builder
+            .description_opt(None)
+            .due_opt(None)
+            .priority_opt(None)
+            .parent_id_opt(None)
+            .section_id_opt(None)
+            .build()
+            .unwrap();
+        assert_eq!(item.description(), "");
+        assert!(item.due().no_date());
+        assert_eq!(item.priority(), &Priority::default());
+        assert_eq!(item.parent_id(), None);
+
+        let item = builder
+            .description_opt(Some("Notes"))
+            .priority_opt(Some(Priority::P1))
+            .section_id_opt(Some(3))
+            .priority_opt(None)
+            .build()
+            .unwrap();
+        assert_eq!(item.description(), "Notes");
+        assert_eq!(item.priority(), &Priority::P1);
+        assert_eq!(item.section_id(), Some(3));
+    }
+
+    #[test]
+    fn effective_priority_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("Task");
+        let by_id: HashMap<u64, Item> = vec![
+            (1, builder.id(1).priority(Priority::P1).build().unwrap()),
+            (
+                2,
+                builder
+                    .id(2)
+                    .priority(Priority::P4)
+                    .parent_id(1)
+                    .build()
+                    .unwrap(),
+            ),
+            (3, builder.id(3).parent_id(3).build().unwrap()),
+        ]
+        .into_iter()
+        .collect();
+
+        let subtask = builder.id(4).parent_id(2).build().unwrap();
+        assert_eq!(effective_priority(&subtask, &by_id), Priority::P1);
+        let own = builder.priority(Priority::P3).build().unwrap();
+        assert_eq!(effective_priority(&own, &by_id), Priority::P3);
+        assert_eq!(effective_priority(&by_id[&3], &by_id), Priority::P4);
+    }
+
+    #[test]
+    fn item_is_set_test() {
+        let mut builder = Item::builder();
+        assert!(!builder.is_set(ItemField::Priority));
+
+        builder.priority(Priority::P4).clear_section();
+        assert!(builder.is_set(ItemField::Priority));
+        assert!(builder.is_set(ItemField::SectionId));
+        assert!(!builder.is_set(ItemField::Content));
+
+        let item = builder
+            .id(1)
+            .user_id(1)
+            .project_id(2)
+            .content("Task")
+            .build()
+            .unwrap();
+        assert!(item.to_builder().unwrap().is_set(ItemField::Description));
+    }
+
+    #[test]
+    fn item_api_json_test() {
+        let item = Item::builder()
+            .id(7)
+            .user_id(1)
+            .project_id(2)
+            .content("Call Bob")
+            .priority(Priority::P1)
+            .label_add(3)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&item).unwrap();
+        assert_eq!(
+            json,
+            r#"{"id":7,"user_id":1,"project_id":2,"content":"Call Bob","description":"","due":null,"priority":4,"parent_id":null,"child_order":0,"day_order":-1,"section_id":null,"labels":[3],"checked":false,"is_deleted":false}"#
+        );
+        assert_eq!(serde_json::from_str::<Item>(&json).unwrap(), item);
+    }
+
+    #[test]
+    fn reparent_on_delete_test() {
+        let task = |id, parent_id| {
+            Item::builder()
+                .id(id)
+                .user_id(1)
+                .project_id(2)
+                .content("Task")
+                .parent_id_opt(parent_id)
+                .build()
+                .unwrap()
+        };
+        let items = || {
+            vec![
+                task(1, None),
+                task(2, Some(1)),
+                task(3, Some(1)),
+                task(4, Some(3)),
+            ]
+        };
+
+        let mut promoted = items();
+        reparent_on_delete(&mut promoted, 1, ReparentMode::PromoteToRoot);
+        let parents: Vec<Option<u64>> = promoted.iter().map(Item::parent_id).collect();
+        assert_eq!(parents, vec![None, None, None, Some(3)]);
+
+        let mut cascaded = items();
+        reparent_on_delete(&mut cascaded, 1, ReparentMode::CascadeDelete);
+        let ids: Vec<Option<u64>> = cascaded.iter().map(Item::id).collect();
+        assert_eq!(ids, vec![Some(1)]);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn item_timestamp_format_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("Task");
+
+        builder
+            .date_added("2021-03-01")
+            .date_completed("2021-03-02T10:00:00Z");
+        let item = builder.build().unwrap();
+        assert_eq!(item.date_added(), Some("2021-03-01"));
+        assert_eq!(item.date_completed(), Some("2021-03-02T10:00:00Z"));
+
+        builder.date_added("1999-13-40");
+        assert_eq!(
+            builder.build(),
+            Err(BuildError::Invalid(BuilderError::InvalidDate("date_added")))
+        );
+
+        builder.date_added("2021-03-01").date_completed("yesterday");
+        assert_eq!(
+            builder.build(),
+            Err(BuildError::Invalid(BuilderError::InvalidDate(
+                "date_completed"
+            )))
+        );
+        assert_eq!(
+            builder.build_checked(),
+            Err(vec![BuilderError::InvalidDate("date_completed")])
+        );
+    }
+
+    #[test]
+    fn item_has_due_time_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("Task");
+        assert!(!builder.build().unwrap().has_due_time());
+
+        builder.due(DueDate::builder().date("2024-05-01").build().unwrap());
+        assert!(!builder.build().unwrap().has_due_time());
+
+        builder.due(
+            DueDate::builder()
+                .datetime("2024-05-01T15:00:00")
+                .build()
+                .unwrap(),
+        );
+        assert!(builder.build().unwrap().has_due_time());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn to_ics_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2);
+        let dated = builder
+            .id(7)
+            .content("Call Bob, then Alice")
+            .due(DueDate::builder().date("2024-05-01").build().unwrap())
+            .build()
+            .unwrap();
+        let timed = builder
+            .id(8)
+            .content("Meeting")
+            .due(
+                DueDate::builder()
+                    .datetime("2024-05-01T15:00:00")
+                    .timezone("America/New_York")
+                    .build()
+                    .unwrap(),
+            )
+            .checked(true)
+            .build()
+            .unwrap();
+        let undated = Item::builder()
+            .id(9)
+            .user_id(1)
+            .project_id(2)
+            .content("Someday")
+            .build()
+            .unwrap();
+
+        let ics = to_ics(&[dated, timed, undated]);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains(
+            "BEGIN:VTODO\r\n\
+             UID:7@todoist.com\r\n\
+             SUMMARY:Call Bob\\, then Alice\r\n\
+             DUE;VALUE=DATE:20240501\r\n\
+             STATUS:NEEDS-ACTION\r\n\
+             END:VTODO\r\n"
+        ));
+        assert!(ics.contains("DUE;TZID=America/New_York:20240501T150000\r\nSTATUS:COMPLETED"));
+        assert!(ics.contains("SUMMARY:Someday\r\nSTATUS:NEEDS-ACTION"));
+        assert_eq!(ics.matches("BEGIN:VTODO").count(), 3);
+    }
+
+    #[test]
+    fn item_labels_from_names_test() {
+        let labels: Vec<Label> = serde_json::from_value(json!([
+            {"id": 3, "name": "Work", "color": 47, "item_order": 1,
+             "is_deleted": false, "is_favorite": false},
+            {"id": 4, "name": "home", "color": 48, "item_order": 2,
+             "is_deleted": false, "is_favorite": false}
+        ]))
+        .unwrap();
+        let index = LabelIndex::new(&labels);
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("Task");
+
+        builder
+            .labels_from_names(&["@work", "Home"], &index)
+            .unwrap();
+        assert_eq!(builder.build().unwrap().labels(), &[3, 4]);
+
+        assert_eq!(
+            builder
+                .labels_from_names(&["work", "@errands"], &index)
+                .unwrap_err(),
+            vec![String::from("@errands")]
+        );
+        assert_eq!(builder.build().unwrap().labels(), &[3, 4]);
+    }
+
+    #[test]
+    fn item_likely_same_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("Task");
+        let item = builder.id(1).build().unwrap();
+
+        assert!(!item.likely_same(&builder.id(2).build().unwrap()));
+        assert!(item.likely_same(&builder.id(1).build().unwrap()));
+        assert!(!item.likely_same(&builder.content("Other").build().unwrap()));
+        let unsaved = Item::builder()
+            .user_id(1)
+            .project_id(2)
+            .content("Task")
+            .build()
+            .unwrap();
+        assert!(!item.likely_same(&unsaved));
+    }
+
+    #[test]
+    fn item_content_length_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2);
+
+        let longest = "é".repeat(MAX_CONTENT_LENGTH);
+        let item = builder.content(&longest).build().unwrap();
+        assert_eq!(item.content(), longest);
+
+        builder.content(&"a".repeat(MAX_CONTENT_LENGTH + 1));
+        assert_eq!(
+            builder.build(),
+            Err(BuildError::Invalid(BuilderError::TooLong {
+                field: "content",
+                max: MAX_CONTENT_LENGTH
+            }))
+        );
+        assert_eq!(
+            builder.build_checked(),
+            Err(vec![BuilderError::TooLong {
+                field: "content",
+                max: MAX_CONTENT_LENGTH,
+            }])
+        );
+    }
+
+    #[test]
+    fn item_merge_from_test() {
+        let mut item = Item::builder()
+            .id(7)
+            .user_id(1)
+            .project_id(2)
+            .content("Call Bob")
+            .description("About the invoice")
+            .priority(Priority::P1)
+            .set_labels(vec![3])
+            .build()
+            .unwrap();
+        let mut expected = item.to_builder().unwrap();
+
+        item.merge_from(&json!({ "content": "Call Alice" }))
+            .unwrap();
+        assert_eq!(item, expected.content("Call Alice").build().unwrap());
+
+        assert!(item.merge_from(&json!({ "priority": "high" })).is_err());
+        assert_eq!(item.priority(), &Priority::P1);
+    }
+
+    #[test]
+    fn item_label_name_test() {
+        let labels = vec![
+            Label::builder().id(1).name("work").build().unwrap(),
+            Label::builder().id(2).name("Errands").build().unwrap(),
+        ];
+        let resolver = LabelResolver::new(&labels);
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(1).content("Buy stamps");
+
+        builder.label_add_name("errands", &resolver).unwrap();
+        builder.label_add_name("@work", &resolver).unwrap();
+        assert_eq!(builder.build().unwrap().labels(), &[2, 1]);
+
+        assert_eq!(
+            builder.label_add_name("someday", &resolver).unwrap_err(),
+            BuilderError::UnknownLabel("someday".to_string())
+        );
+        assert_eq!(builder.build().unwrap().labels(), &[2, 1]);
+
+        builder.label_remove_name("Work", &resolver).unwrap();
+        assert_eq!(builder.build().unwrap().labels(), &[2]);
+    }
+
+    #[test]
+    fn item_assignment_test() {
+        let item: Item = serde_json::from_value(json!({
+            "id": 7,
+            "user_id": 1,
+            "project_id": 2,
+            "content": "Review the deck",
+            "description": "",
+            "due": null,
+            "priority": 1,
+            "parent_id": null,
+            "child_order": 1,
+            "section_id": null,
+            "labels": [],
+            "checked": 0,
+            "is_deleted": 0,
+            "responsible_uid": 5,
+            "assigned_by_uid": 1
+        }))
+        .unwrap();
+        assert_eq!(item.responsible_uid(), Some(5));
+        assert_eq!(item.assigned_by_uid(), Some(1));
+        let json = serde_json::to_value(&item).unwrap();
+        assert_eq!(serde_json::from_value::<Item>(json).unwrap(), item);
+
+        let args = item
+            .to_builder()
+            .unwrap()
+            .responsible_uid(6)
+            .update_args()
+            .unwrap();
+        assert_eq!(args["responsible_uid"], json!(6));
+        assert!(args.get("assigned_by_uid").is_none());
+    }
+
+    #[test]
+    fn item_display_test() {
+        let mut builder = Item::builder();
+        builder.user_id(1).project_id(2).content("Call Bob");
+        assert_eq!(builder.build().unwrap().to_string(), "Call Bob [P4]");
+
+        let due = DueDate::builder()
+            .date("2021-03-01")
+            .string("every monday")
+            .build()
+            .unwrap();
+        let item = builder.priority(Priority::P1).due(due).build().unwrap();
+        assert_eq!(item.to_string(), "Call Bob [P1] (every monday)");
+    }
+
+    #[test]
+    fn item_clone_test() {
+        let item = Item::builder()
+            .user_id(1)
+            .project_id(2)
+            .content("Call Bob")
+            .build()
+            .unwrap();
+        assert!(item.to_builder().is_err());
+        assert_eq!(item.clone(), item);
+    }
+
+    #[test]
+    fn item_from_rest_v2_test() {
+        let rest = json!({
+            "creator_id": "2671355",
+            "created_at": "2019-12-11T22:36:50.000000Z",
+            "assignee_id": "2671362",
+            "assigner_id": "2671355",
+            "comment_count": 10,
+            "is_completed": true,
+            "content": "Buy Milk",
+            "description": "",
+            "due": {
+                "date": "2016-09-01",
+                "is_recurring": false,
+                "datetime": "2016-09-01T12:00:00.000000Z",
+                "string": "tomorrow at 12",
+                "timezone": "Europe/Moscow"
+            },
+            "duration": null,
+            "id": "2995104339",
+            "labels": ["Food", "Shopping"],
+            "order": 1,
+            "priority": 4,
+            "project_id": "2203306141",
+            "section_id": "7025",
+            "parent_id": null,
+            "url": "https://todoist.com/showTask?id=2995104339"
+        });
+
+        let item = Item::from_rest_v2(&rest).unwrap();
+        assert_eq!(item.id(), Some(2995104339));
+        assert_eq!(item.project_id(), 2203306141);
+        assert_eq!(item.section_id(), Some(7025));
+        assert_eq!(item.parent_id(), None);
+        assert_eq!(item.content(), "Buy Milk");
+        assert_eq!(item.priority(), &Priority::P1);
+        assert!(item.checked());
+        assert!(item.labels().is_empty());
+        assert_eq!(item.date_added(), Some("2019-12-11T22:36:50.000000Z"));
+        assert_eq!(item.responsible_uid(), Some(2671362));
+        assert_eq!(item.due().date(), "2016-09-01T12:00:00Z");
+        assert_eq!(item.due().timezone(), Some("Europe/Moscow"));
+        assert_eq!(item.due().string(), "tomorrow at 12");
+
+        let mut invalid = rest;
+        invalid["project_id"] = json!("inbox");
+        let error = Item::from_rest_v2(&invalid).unwrap_err();
+        assert_eq!(error.to_string(), "invalid project_id \"inbox\"");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn time_conflicts_test() {
+        let task = |id: u64, date: &str, minutes: Option<u32>| {
+            let mut builder = Item::builder();
+            builder
+                .id(id)
+                .user_id(1)
+                .project_id(2)
+                .content("Focus")
+                .due(DueDate::builder().date(date).build().unwrap());
+            if let Some(minutes) = minutes {
+                builder.duration(TaskDuration::new(minutes, DurationUnit::Minute));
+            }
+            builder.build().unwrap()
+        };
+        let items = vec![
+            task(1, "2024-05-01T15:00:00", Some(60)),
+            task(2, "2024-05-01T15:30:00", Some(30)),
+            task(3, "2024-05-01T16:00:00", Some(30)),
+            task(4, "2024-05-01", None),
+            task(5, "2024-05-02T15:30:00", None),
+            task(6, "2024-05-01T23:00:00", Some(180)),
+            task(7, "2024-05-02T01:00:00", Some(30)),
+        ];
+
+        assert_eq!(time_conflicts(&items), vec![(1, 2)]);
+
+        let mut endless = task(8, "2024-05-01T15:15:00", None);
+        endless
+            .merge_from(&json!({"duration": {"amount": u32::MAX, "unit": "day"}}))
+            .unwrap();
+        let mut items = items;
+        items.push(endless);
+        assert_eq!(time_conflicts(&items), vec![(1, 2)]);
+    }
+
+    #[test]
+    fn item_duration_test() {
+        let mut item = Item::builder()
+            .id(1)
+            .user_id(1)
+            .project_id(2)
+            .content("Write report")
+            .build()
+            .unwrap();
+        assert!(serde_json::to_value(&item)
+            .unwrap()
+            .get("duration")
+            .is_none());
+
+        item.merge_from(&json!({"duration": {"amount": 2, "unit": "day"}}))
+            .unwrap();
+        let duration = item.duration().unwrap();
+        assert_eq!(duration, TaskDuration::new(2, DurationUnit::Day));
+        assert_eq!(duration.minutes(), 2 * 24 * 60);
+        let args = item.to_builder().unwrap().update_args().unwrap();
+        assert_eq!(args["duration"], json!({"amount": 2, "unit": "day"}));
+    }
+}
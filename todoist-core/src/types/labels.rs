@@ -0,0 +1,357 @@
+//! Labels, which can be attached to any number of tasks.
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::colors::Colors;
+use crate::types::error::{self, BuildError, BuilderError, Resource};
+use crate::types::favorite_unless_deleted;
+use crate::types::items::Item;
+
+/// JSON keys of the API object that [`Label`] deserializes into named fields,
+/// see [`super::unmodeled_fields`].
+pub(crate) const FIELDS: &[&str] = &[
+    "id",
+    "name",
+    "color",
+    "item_order",
+    "is_deleted",
+    "is_favorite",
+];
+
+/// A Todoist label as returned by the Sync API.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Label {
+    id: Option<u64>,
+    name: String,
+    color: Colors,
+    item_order: u32,
+    #[serde(deserialize_with = "crate::types::flag")]
+    is_deleted: bool,
+    #[serde(deserialize_with = "crate::types::flag")]
+    is_favorite: bool,
+}
+
+impl Label {
+    pub fn builder() -> LabelBuilder {
+        LabelBuilder::default()
+    }
+
+    /// Starts an edit of an existing label. Only labels that came from the API,
+    /// and therefore have an id, can be updated.
+    pub fn to_builder(&self) -> Result<LabelBuilder, BuildError> {
+        if self.id.is_none() {
+            return Err(BuildError::NotSaved(Resource::Label));
+        }
+
+        Ok(LabelBuilder {
+            id: self.id,
+            name: Some(self.name.clone()),
+            color: Some(self.color),
+            item_order: Some(self.item_order),
+            is_deleted: Some(self.is_deleted),
+            is_favorite: Some(self.is_favorite),
+        })
+    }
+
+    pub fn id(&self) -> Option<u64> {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn color(&self) -> Colors {
+        self.color
+    }
+
+    pub fn item_order(&self) -> u32 {
+        self.item_order
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.is_deleted
+    }
+
+    pub fn is_favorite(&self) -> bool {
+        self.is_favorite
+    }
+
+    /// Shifts the label's id by `offset`, see [`crate::store::Store::merge`].
+    pub(crate) fn offset_ids(&mut self, offset: u64) {
+        self.id = self.id.map(|id| id.wrapping_add(offset));
+    }
+}
+
+/// The label's name, without the `@` of queries.
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// The labels no non-deleted item of `items` is labeled with, in the order of
+/// `labels`. Labels that are deleted already, or were never saved, are left
+/// out.
+pub fn unused<'a>(labels: &'a [Label], items: &[Item]) -> Vec<&'a Label> {
+    let used: HashSet<u64> = items
+        .iter()
+        .filter(|item| !item.is_deleted())
+        .flat_map(|item| item.labels().iter().copied())
+        .collect();
+    labels
+        .iter()
+        .filter(|label| !label.is_deleted)
+        .filter(|label| label.id.is_some_and(|id| !used.contains(&id)))
+        .collect()
+}
+
+/// Looks labels up by name or id, e.g. to resolve `@name` in a query or to
+/// display the labels of an item. Deleted labels are left out.
+///
+/// Like in Todoist, names are matched case-insensitively; `name_of` still gives
+/// the name with its original casing.
+#[derive(Debug, Default)]
+pub struct LabelIndex {
+    names: HashMap<u64, String>,
+    ids: HashMap<String, u64>,
+}
+
+impl LabelIndex {
+    pub fn new<'a, I: IntoIterator<Item = &'a Label>>(labels: I) -> Self {
+        let mut index = LabelIndex::default();
+        for label in labels {
+            if let (Some(id), false) = (label.id, label.is_deleted) {
+                index.names.insert(id, label.name.clone());
+                index.ids.insert(label.name.to_lowercase(), id);
+            }
+        }
+        index
+    }
+
+    pub fn id_of(&self, name: &str) -> Option<u64> {
+        self.ids.get(&name.to_lowercase()).copied()
+    }
+
+    pub fn name_of(&self, id: u64) -> Option<&str> {
+        self.names.get(&id).map(String::as_str)
+    }
+}
+
+/// Resolves the label names a user types to the ids the builders take, see
+/// [`ItemBuilder::label_add_name`](crate::types::items::ItemBuilder::label_add_name).
+pub type LabelResolver = LabelIndex;
+
+/// The fields of a label, see [`LabelBuilder::is_set`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LabelField {
+    Id,
+    Name,
+    Color,
+    ItemOrder,
+    IsDeleted,
+    IsFavorite,
+}
+
+#[derive(Debug, Default)]
+pub struct LabelBuilder {
+    id: Option<u64>,
+    name: Option<String>,
+    color: Option<Colors>,
+    item_order: Option<u32>,
+    is_deleted: Option<bool>,
+    is_favorite: Option<bool>,
+}
+
+impl LabelBuilder {
+    pub fn id(&mut self, value: u64) -> &mut Self {
+        self.id = Some(value);
+        self
+    }
+
+    /// Leading and trailing whitespace is dropped. Unlike project and section
+    /// names, label names aren't checked against a length limit, so one the
+    /// API rejects only fails once it is sent.
+    pub fn name(&mut self, value: &str) -> &mut Self {
+        self.name = Some(value.trim().to_string());
+        self
+    }
+
+    pub fn color(&mut self, value: Colors) -> &mut Self {
+        self.color = Some(value);
+        self
+    }
+
+    pub fn item_order(&mut self, value: u32) -> &mut Self {
+        self.item_order = Some(value);
+        self
+    }
+
+    pub fn is_deleted(&mut self, value: bool) -> &mut Self {
+        self.is_deleted = Some(value);
+        self
+    }
+
+    pub fn is_favorite(&mut self, value: bool) -> &mut Self {
+        self.is_favorite = Some(value);
+        self
+    }
+
+    /// Whether `field` was given a value, as opposed to being left to its
+    /// default.
+    pub fn is_set(&self, field: LabelField) -> bool {
+        match field {
+            LabelField::Id => self.id.is_some(),
+            LabelField::Name => self.name.is_some(),
+            LabelField::Color => self.color.is_some(),
+            LabelField::ItemOrder => self.item_order.is_some(),
+            LabelField::IsDeleted => self.is_deleted.is_some(),
+            LabelField::IsFavorite => self.is_favorite.is_some(),
+        }
+    }
+
+    /// Every problem with the fields set so far, in field order.
+    fn validate(&self) -> Vec<BuilderError> {
+        let mut errors = Vec::new();
+        if self.name.is_none() {
+            errors.push(BuilderError::Missing("name"));
+        }
+        errors
+    }
+
+    /// Like `build`, but reports every problem at once instead of stopping at
+    /// the first one.
+    pub fn build_checked(&self) -> Result<Label, Vec<BuilderError>> {
+        let errors = self.validate();
+        let name = match &self.name {
+            Some(name) if errors.is_empty() => name.clone(),
+            _ => return Err(errors),
+        };
+        let is_deleted = self.is_deleted.unwrap_or(false);
+
+        Ok(Label {
+            id: self.id,
+            name,
+            color: self.color.unwrap_or_default(),
+            item_order: self.item_order.unwrap_or_default(),
+            is_deleted,
+            is_favorite: favorite_unless_deleted(
+                "label",
+                self.is_favorite.unwrap_or(false),
+                is_deleted,
+            ),
+        })
+    }
+
+    pub fn build(&self) -> Result<Label, BuildError> {
+        self.build_checked()
+            .map_err(|errors| error::first(errors, Resource::Label))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn label_error_test() {
+        assert_eq!(
+            Label::builder().build(),
+            Err(BuildError::MissingName(Resource::Label))
+        );
+        let label = Label::builder().name("work").build().unwrap();
+        assert!(label.to_builder().is_err());
+    }
+
+    #[test]
+    fn label_create_test() {
+        let label = Label::builder()
+            .name("work")
+            .color(Colors::Blue)
+            .build()
+            .unwrap();
+
+        assert_eq!(label.name(), "work");
+        assert_eq!(label.color(), Colors::Blue);
+        assert!(!label.is_favorite());
+    }
+
+    #[test]
+    fn label_update_test() {
+        let label = Label::builder().id(1).name("work").build().unwrap();
+        let updated = label.to_builder().unwrap().name("office").build().unwrap();
+
+        assert_eq!(updated.id(), Some(1));
+        assert_eq!(updated.name(), "office");
+    }
+
+    #[test]
+    fn label_deleted_favorite_test() {
+        let label = Label::builder()
+            .name("old")
+            .is_favorite(true)
+            .is_deleted(true)
+            .build()
+            .unwrap();
+
+        assert!(label.is_deleted());
+        assert!(!label.is_favorite());
+    }
+
+    #[test]
+    fn label_index_test() {
+        let labels = vec![
+            Label::builder().id(1).name("work").build().unwrap(),
+            Label::builder().id(2).name("home").build().unwrap(),
+            Label::builder()
+                .id(3)
+                .name("old")
+                .is_deleted(true)
+                .build()
+                .unwrap(),
+        ];
+        let index = LabelIndex::new(&labels);
+
+        assert_eq!(index.id_of("work"), Some(1));
+        assert_eq!(index.name_of(2), Some("home"));
+        assert_eq!(index.id_of("old"), None);
+    }
+
+    #[test]
+    fn label_index_case_insensitive_test() {
+        let labels = vec![Label::builder().id(1).name("Work").build().unwrap()];
+        let index = LabelIndex::new(&labels);
+
+        assert_eq!(index.id_of("work"), Some(1));
+        assert_eq!(index.id_of("WORK"), Some(1));
+        assert_eq!(index.name_of(1), Some("Work"));
+    }
+
+    #[test]
+    fn unused_labels_test() {
+        let labels = vec![
+            Label::builder().id(1).name("work").build().unwrap(),
+            Label::builder().id(2).name("home").build().unwrap(),
+            Label::builder().id(3).name("old").build().unwrap(),
+        ];
+        let item = |labels: Vec<u64>, deleted: bool| {
+            Item::builder()
+                .user_id(1)
+                .project_id(1)
+                .content("Task")
+                .set_labels(labels)
+                .is_deleted(deleted)
+                .build()
+                .unwrap()
+        };
+        let items = vec![item(vec![1], false), item(vec![3], true)];
+
+        let unused: Vec<&str> = unused(&labels, &items)
+            .into_iter()
+            .map(|label| label.name.as_str())
+            .collect();
+        assert_eq!(unused, vec!["home", "old"]);
+    }
+}
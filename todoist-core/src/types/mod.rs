@@ -0,0 +1,161 @@
+//! Data types mirroring the resources of the Todoist Sync API.
+use serde::de::{Error, Unexpected};
+use std::convert::TryFrom;
+
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use tracing::warn;
+
+pub mod collaborators;
+pub mod colors;
+pub mod dates;
+pub mod error;
+pub mod filters;
+pub mod items;
+pub mod labels;
+pub mod notes;
+pub mod order;
+pub mod priority;
+pub mod projects;
+pub mod reminders;
+pub mod resources;
+pub mod sections;
+pub mod stats;
+pub mod user;
+
+/// The resource types [`unmodeled_fields`] knows the fields of.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EntityKind {
+    Item,
+    Project,
+    Section,
+    Label,
+    Filter,
+    Note,
+    Reminder,
+    User,
+}
+
+/// Keys of a raw API object that the matching struct of this crate has no
+/// field for, sorted. The API gains fields over time, and this is a quick way
+/// to find out which ones are missing when reporting it. Anything but a JSON
+/// object has no keys to report.
+pub fn unmodeled_fields(json: &Value, entity: EntityKind) -> Vec<String> {
+    let known = match entity {
+        EntityKind::Item => items::FIELDS,
+        EntityKind::Project => projects::FIELDS,
+        EntityKind::Section => sections::FIELDS,
+        EntityKind::Label => labels::FIELDS,
+        EntityKind::Filter => filters::FIELDS,
+        EntityKind::Note => notes::FIELDS,
+        EntityKind::Reminder => reminders::FIELDS,
+        EntityKind::User => user::FIELDS,
+    };
+    let mut fields: Vec<String> = match json {
+        Value::Object(object) => object
+            .keys()
+            .filter(|key| !known.contains(&key.as_str()))
+            .cloned()
+            .collect(),
+        _ => Vec::new(),
+    };
+    fields.sort();
+    fields
+}
+
+/// Reads a boolean flag sent either as `true`/`false` or, like version 8 of
+/// the Sync API does, as `1`/`0`.
+pub(crate) fn flag<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Flag {
+        Bool(bool),
+        Int(u64),
+    }
+
+    match Flag::deserialize(deserializer)? {
+        Flag::Bool(value) => Ok(value),
+        Flag::Int(0) => Ok(false),
+        Flag::Int(1) => Ok(true),
+        Flag::Int(value) => Err(D::Error::invalid_value(
+            Unexpected::Unsigned(value),
+            &"a boolean, 0 or 1",
+        )),
+    }
+}
+
+/// Reads the integer discriminant of an enum like [`colors::Colors`]. A value
+/// that is no discriminant fails with the message of
+/// [`error::BuilderError::UnknownDiscriminant`], naming `field` and the value.
+pub(crate) fn discriminant<'de, D, T>(deserializer: D, field: &'static str) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<u8>,
+{
+    let value = u64::deserialize(deserializer)?;
+    u8::try_from(value)
+        .ok()
+        .and_then(|id| T::try_from(id).ok())
+        .ok_or_else(|| D::Error::custom(error::BuilderError::UnknownDiscriminant { field, value }))
+}
+
+/// A deleted resource cannot sensibly be a favorite, and the apps get confused
+/// when one is. Rather than failing the build, the favorite flag is dropped.
+pub(crate) fn favorite_unless_deleted(kind: &str, is_favorite: bool, is_deleted: bool) -> bool {
+    if is_favorite && is_deleted {
+        warn!(kind, "clearing is_favorite on a deleted resource");
+        return false;
+    }
+    is_favorite
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::items::Item;
+    use serde_json::json;
+
+    #[test]
+    fn flag_test() {
+        let item: Item = serde_json::from_str(
+            r#"{"id": 7, "user_id": 1, "project_id": 2, "content": "Call Bob",
+                "description": "", "due": null, "priority": 1,
+                "parent_id": null, "child_order": 1, "section_id": null,
+                "labels": [], "checked": 1, "is_deleted": 0}"#,
+        )
+        .unwrap();
+        assert!(item.checked());
+        assert!(!item.is_deleted());
+
+        let json = serde_json::to_value(&item).unwrap();
+        assert_eq!(json["checked"], json!(true));
+        assert!(serde_json::from_value::<Item>(json).unwrap().checked());
+
+        let invalid = r#"{"id": 7, "user_id": 1, "project_id": 2, "content": "Call Bob",
+            "description": "", "due": null, "priority": 1, "parent_id": null,
+            "child_order": 1, "section_id": null, "labels": [], "checked": 2,
+            "is_deleted": 0}"#;
+        assert!(serde_json::from_str::<Item>(invalid).is_err());
+    }
+
+    #[test]
+    fn unmodeled_fields_test() {
+        let item = Item::builder()
+            .id(1)
+            .user_id(2)
+            .project_id(3)
+            .content("Buy milk")
+            .build()
+            .unwrap();
+        let mut json = serde_json::to_value(&item).unwrap();
+        assert!(unmodeled_fields(&json, EntityKind::Item).is_empty());
+
+        json["note_count"] = json!(0);
+        json["added_at"] = json!("2021-04-01T10:00:00Z");
+        assert_eq!(
+            unmodeled_fields(&json, EntityKind::Item),
+            vec!["added_at", "note_count"]
+        );
+        assert!(unmodeled_fields(&json!([1]), EntityKind::Item).is_empty());
+    }
+}
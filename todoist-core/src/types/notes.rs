@@ -0,0 +1,340 @@
+//! Notes, which the apps call comments, on a task or a project.
+use serde::{Deserialize, Serialize};
+
+use crate::types::dates;
+use crate::types::error::{self, BuildError, BuilderError, Resource};
+
+/// JSON keys of the API object that [`Note`] deserializes into named fields,
+/// see [`super::unmodeled_fields`].
+pub(crate) const FIELDS: &[&str] = &[
+    "id",
+    "item_id",
+    "project_id",
+    "content",
+    "posted",
+    "file_attachment",
+    "is_deleted",
+];
+
+/// A file uploaded along with a note.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct FileAttachment {
+    file_name: String,
+    file_type: String,
+    file_url: String,
+    file_size: u64,
+}
+
+impl FileAttachment {
+    /// An attachment of the file at `file_url`, of MIME type `file_type` and
+    /// `file_size` bytes.
+    pub fn new(file_name: &str, file_type: &str, file_url: &str, file_size: u64) -> Self {
+        FileAttachment {
+            file_name: file_name.to_string(),
+            file_type: file_type.to_string(),
+            file_url: file_url.to_string(),
+            file_size,
+        }
+    }
+
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    pub fn file_type(&self) -> &str {
+        &self.file_type
+    }
+
+    pub fn file_url(&self) -> &str {
+        &self.file_url
+    }
+
+    pub fn file_size(&self) -> u64 {
+        self.file_size
+    }
+}
+
+/// A Todoist note as returned by the Sync API. Notes on a task have an
+/// `item_id`, notes on a project a `project_id`.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Note {
+    id: Option<u64>,
+    #[serde(default)]
+    item_id: Option<u64>,
+    #[serde(default)]
+    project_id: Option<u64>,
+    content: String,
+    #[serde(default)]
+    posted: Option<String>,
+    #[serde(default)]
+    file_attachment: Option<FileAttachment>,
+    #[serde(deserialize_with = "crate::types::flag")]
+    is_deleted: bool,
+}
+
+impl Note {
+    pub fn builder() -> NoteBuilder {
+        NoteBuilder::default()
+    }
+
+    /// Starts an edit of an existing note. Only notes that came from the API,
+    /// and therefore have an id, can be updated.
+    pub fn to_builder(&self) -> Result<NoteBuilder, BuildError> {
+        if self.id.is_none() {
+            return Err(BuildError::NotSaved(Resource::Note));
+        }
+
+        Ok(NoteBuilder {
+            id: self.id,
+            item_id: self.item_id,
+            project_id: self.project_id,
+            content: Some(self.content.clone()),
+            posted: self.posted.clone(),
+            file_attachment: self.file_attachment.clone(),
+            is_deleted: Some(self.is_deleted),
+        })
+    }
+
+    pub fn id(&self) -> Option<u64> {
+        self.id
+    }
+
+    pub fn item_id(&self) -> Option<u64> {
+        self.item_id
+    }
+
+    pub fn project_id(&self) -> Option<u64> {
+        self.project_id
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// When the note was posted, as a `YYYY-MM-DD` date or an RFC 3339 date
+    /// and time.
+    pub fn posted(&self) -> Option<&str> {
+        self.posted.as_deref()
+    }
+
+    pub fn file_attachment(&self) -> Option<&FileAttachment> {
+        self.file_attachment.as_ref()
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.is_deleted
+    }
+}
+
+/// The fields of a note, see [`NoteBuilder::is_set`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NoteField {
+    Id,
+    ItemId,
+    ProjectId,
+    Content,
+    Posted,
+    FileAttachment,
+    IsDeleted,
+}
+
+#[derive(Debug, Default)]
+pub struct NoteBuilder {
+    id: Option<u64>,
+    item_id: Option<u64>,
+    project_id: Option<u64>,
+    content: Option<String>,
+    posted: Option<String>,
+    file_attachment: Option<FileAttachment>,
+    is_deleted: Option<bool>,
+}
+
+impl NoteBuilder {
+    pub fn id(&mut self, value: u64) -> &mut Self {
+        self.id = Some(value);
+        self
+    }
+
+    /// Attaches the note to the task with this id.
+    pub fn item_id(&mut self, value: u64) -> &mut Self {
+        self.item_id = Some(value);
+        self
+    }
+
+    /// Attaches the note to the project with this id.
+    pub fn project_id(&mut self, value: u64) -> &mut Self {
+        self.project_id = Some(value);
+        self
+    }
+
+    /// Leading and trailing whitespace is dropped.
+    pub fn content(&mut self, value: &str) -> &mut Self {
+        self.content = Some(value.trim().to_string());
+        self
+    }
+
+    /// Sets when the note was posted, as a `YYYY-MM-DD` date or an RFC 3339
+    /// date and time.
+    pub fn posted(&mut self, value: &str) -> &mut Self {
+        self.posted = Some(value.to_string());
+        self
+    }
+
+    pub fn file_attachment(&mut self, value: FileAttachment) -> &mut Self {
+        self.file_attachment = Some(value);
+        self
+    }
+
+    pub fn is_deleted(&mut self, value: bool) -> &mut Self {
+        self.is_deleted = Some(value);
+        self
+    }
+
+    /// Whether `field` was given a value, as opposed to being left to its
+    /// default.
+    pub fn is_set(&self, field: NoteField) -> bool {
+        match field {
+            NoteField::Id => self.id.is_some(),
+            NoteField::ItemId => self.item_id.is_some(),
+            NoteField::ProjectId => self.project_id.is_some(),
+            NoteField::Content => self.content.is_some(),
+            NoteField::Posted => self.posted.is_some(),
+            NoteField::FileAttachment => self.file_attachment.is_some(),
+            NoteField::IsDeleted => self.is_deleted.is_some(),
+        }
+    }
+
+    /// Every problem with the fields set so far, in field order.
+    fn validate(&self) -> Vec<BuilderError> {
+        let mut errors = Vec::new();
+        if self.content.is_none() {
+            errors.push(BuilderError::Missing("content"));
+        }
+        if self.item_id.is_none() && self.project_id.is_none() {
+            errors.push(BuilderError::Missing("item_id or project_id"));
+        }
+        if let Err(error) = dates::check_timestamp("posted", self.posted.as_deref()) {
+            errors.push(error);
+        }
+        errors
+    }
+
+    /// Like `build`, but reports every problem at once instead of stopping at
+    /// the first one.
+    pub fn build_checked(&self) -> Result<Note, Vec<BuilderError>> {
+        let errors = self.validate();
+        let content = match &self.content {
+            Some(content) if errors.is_empty() => content.clone(),
+            _ => return Err(errors),
+        };
+
+        Ok(Note {
+            id: self.id,
+            item_id: self.item_id,
+            project_id: self.project_id,
+            content,
+            posted: self.posted.clone(),
+            file_attachment: self.file_attachment.clone(),
+            is_deleted: self.is_deleted.unwrap_or(false),
+        })
+    }
+
+    pub fn build(&self) -> Result<Note, BuildError> {
+        self.build_checked()
+            .map_err(|errors| error::first(errors, Resource::Note))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn note_error_test() {
+        assert_eq!(
+            Note::builder().item_id(7).build(),
+            Err(BuildError::MissingContent(Resource::Note))
+        );
+        assert_eq!(
+            Note::builder().content("Call first").build(),
+            Err(BuildError::MissingNoteParent)
+        );
+        assert_eq!(
+            Note::builder().build_checked(),
+            Err(vec![
+                BuilderError::Missing("content"),
+                BuilderError::Missing("item_id or project_id"),
+            ])
+        );
+        assert_eq!(
+            Note::builder()
+                .item_id(7)
+                .content("Call first")
+                .build()
+                .unwrap()
+                .to_builder()
+                .unwrap_err(),
+            BuildError::NotSaved(Resource::Note)
+        );
+    }
+
+    #[test]
+    fn note_content_trim_test() {
+        let note = Note::builder()
+            .item_id(7)
+            .content("  Foo  ")
+            .build()
+            .unwrap();
+        assert_eq!(note.content(), "Foo");
+    }
+
+    #[test]
+    fn note_create_test() {
+        let attachment = FileAttachment::new(
+            "agenda.pdf",
+            "application/pdf",
+            "https://example.com/agenda.pdf",
+            1024,
+        );
+        let note = Note::builder()
+            .item_id(7)
+            .content("Agenda attached")
+            .file_attachment(attachment.clone())
+            .build()
+            .unwrap();
+
+        assert_eq!(note.item_id(), Some(7));
+        assert_eq!(note.project_id(), None);
+        assert_eq!(note.content(), "Agenda attached");
+        assert_eq!(note.file_attachment(), Some(&attachment));
+        assert!(!note.is_deleted());
+
+        let json = serde_json::to_value(&note).unwrap();
+        assert_eq!(json["file_attachment"]["file_size"], json!(1024));
+        assert_eq!(serde_json::from_value::<Note>(json).unwrap(), note);
+    }
+
+    #[test]
+    fn note_update_test() {
+        let note: Note = serde_json::from_value(json!({
+            "id": 3,
+            "project_id": 2,
+            "content": "Kickoff on Monday",
+            "posted": "2021-03-01T10:00:00Z",
+            "file_attachment": null,
+            "is_deleted": 0
+        }))
+        .unwrap();
+
+        let mut builder = note.to_builder().unwrap();
+        assert!(builder.is_set(NoteField::ProjectId));
+        assert!(!builder.is_set(NoteField::ItemId));
+        let updated = builder.content("Kickoff on Tuesday").build().unwrap();
+
+        assert_eq!(updated.id(), Some(3));
+        assert_eq!(updated.project_id(), Some(2));
+        assert_eq!(updated.content(), "Kickoff on Tuesday");
+        assert_eq!(updated.posted(), Some("2021-03-01T10:00:00Z"));
+    }
+}
@@ -0,0 +1,103 @@
+//! Helpers for the `child_order`/`section_order` fields siblings are sorted by.
+//!
+//! The API only cares about the relative order of siblings, but the apps keep
+//! the values contiguous starting at 1, and so do these helpers.
+use std::convert::TryFrom;
+
+use tracing::warn;
+
+/// How many times bigger than the number of siblings an order value can be
+/// before it is reported as suspicious.
+const SUSPICIOUS_ORDER_FACTOR: u64 = 10;
+
+/// Something positioned among its siblings by an order value.
+pub trait Ordered {
+    fn order(&self) -> u32;
+    fn set_order(&mut self, order: u32);
+}
+
+/// Indices of `siblings`, sorted by their order value. Ties keep their
+/// position in the slice.
+fn ranking<T: Ordered>(siblings: &[T]) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..siblings.len()).collect();
+    indices.sort_by_key(|index| siblings[*index].order());
+    indices
+}
+
+/// Assigns order values `1..` to the siblings listed in `ranking`.
+fn assign<T: Ordered>(siblings: &mut [T], ranking: &[usize]) {
+    for (rank, index) in ranking.iter().enumerate() {
+        let order = u32::try_from(rank).map_or(u32::MAX, |rank| rank.saturating_add(1));
+        siblings[*index].set_order(order);
+    }
+}
+
+/// Rewrites the order values of `siblings` to be contiguous from 1 while
+/// keeping their relative order. The slice itself is not rearranged.
+pub fn normalize_order<T: Ordered>(siblings: &mut [T]) {
+    let limit = (siblings.len() as u64).saturating_mul(SUSPICIOUS_ORDER_FACTOR);
+    if let Some(order) = siblings
+        .iter()
+        .map(Ordered::order)
+        .find(|order| u64::from(*order) > limit)
+    {
+        warn!(
+            order,
+            siblings = siblings.len(),
+            "order value is far outside the number of siblings"
+        );
+    }
+
+    let ranking = ranking(siblings);
+    assign(siblings, &ranking);
+}
+
+/// Moves the sibling at `index` to `position` (0 based, clamped to the end)
+/// among its siblings, normalizing all order values in the process.
+pub fn reorder<T: Ordered>(siblings: &mut [T], index: usize, position: usize) {
+    let mut ranking = ranking(siblings);
+    if let Some(from) = ranking.iter().position(|i| *i == index) {
+        ranking.remove(from);
+        ranking.insert(position.min(ranking.len()), index);
+    }
+    assign(siblings, &ranking);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Ordered for u32 {
+        fn order(&self) -> u32 {
+            *self
+        }
+
+        fn set_order(&mut self, order: u32) {
+            *self = order;
+        }
+    }
+
+    #[test]
+    fn normalize_order_test() {
+        let mut orders = vec![30, 10, 20, 10];
+        normalize_order(&mut orders);
+        assert_eq!(orders, vec![4, 1, 3, 2]);
+    }
+
+    #[test]
+    fn normalize_order_large_test() {
+        let mut orders: Vec<u32> = (0..100_000).map(|i| u32::MAX - i).collect();
+        normalize_order(&mut orders);
+        assert_eq!(orders[0], 100_000);
+        assert_eq!(orders[99_999], 1);
+    }
+
+    #[test]
+    fn reorder_test() {
+        let mut orders = vec![1, 2, 3, 4];
+        reorder(&mut orders, 3, 0);
+        assert_eq!(orders, vec![2, 3, 4, 1]);
+        reorder(&mut orders, 0, 100);
+        assert_eq!(orders, vec![4, 2, 3, 1]);
+    }
+}
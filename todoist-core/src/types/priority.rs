@@ -0,0 +1,125 @@
+//! Task priority.
+//!
+//! The Todoist clients number priorities the opposite way from the API: the
+//! highest priority is shown as `P1` in the apps but sent over the wire as `4`.
+//! `Priority` always uses the client-facing numbering, and is only converted to
+//! the API numbering when serialized.
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Priority of a task, using the client-facing numbering.
+#[derive(Serialize, Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[serde(into = "u8")]
+pub enum Priority {
+    /// Urgent, red flag in the apps.
+    P1,
+    /// High, orange flag in the apps.
+    P2,
+    /// Medium, blue flag in the apps.
+    P3,
+    /// Natural priority, the default for new tasks.
+    #[default]
+    P4,
+}
+
+impl Priority {
+    /// The number the API uses for this priority, from 4 for `P1` down to 1
+    /// for `P4`.
+    pub fn as_api_int(&self) -> u8 {
+        match self {
+            Priority::P1 => 4,
+            Priority::P2 => 3,
+            Priority::P3 => 2,
+            Priority::P4 => 1,
+        }
+    }
+}
+
+/// The client-facing name, `P1` to `P4`.
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Priority::P1 => "P1",
+            Priority::P2 => "P2",
+            Priority::P3 => "P3",
+            Priority::P4 => "P4",
+        };
+        f.write_str(name)
+    }
+}
+
+impl From<Priority> for u8 {
+    fn from(priority: Priority) -> u8 {
+        priority.as_api_int()
+    }
+}
+
+/// A number outside of the `1..=4` range the API uses for priorities.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct InvalidPriority(pub u8);
+
+impl fmt::Display for InvalidPriority {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid priority {}, expected 1 to 4", self.0)
+    }
+}
+
+impl Error for InvalidPriority {}
+
+/// Converts from the API numbering, see [`Priority::as_api_int`].
+impl TryFrom<u8> for Priority {
+    type Error = InvalidPriority;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            4 => Ok(Priority::P1),
+            3 => Ok(Priority::P2),
+            2 => Ok(Priority::P3),
+            1 => Ok(Priority::P4),
+            other => Err(InvalidPriority(other)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Priority {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::types::discriminant(deserializer, "priority")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn priority_api_int_test() {
+        let all = [
+            (4, Priority::P1),
+            (3, Priority::P2),
+            (2, Priority::P3),
+            (1, Priority::P4),
+        ];
+        for (int, priority) in all.iter() {
+            assert_eq!(Priority::try_from(*int), Ok(*priority));
+            assert_eq!(priority.as_api_int(), *int);
+        }
+        assert_eq!(Priority::try_from(0), Err(InvalidPriority(0)));
+        assert_eq!(Priority::try_from(5), Err(InvalidPriority(5)));
+    }
+
+    #[test]
+    fn priority_serde_test() {
+        assert_eq!(serde_json::to_string(&Priority::P1).unwrap(), "4");
+        assert_eq!(serde_json::from_str::<Priority>("1").unwrap(), Priority::P4);
+        assert_eq!(
+            serde_json::from_str::<Priority>("5")
+                .unwrap_err()
+                .to_string(),
+            "Unknown priority 5"
+        );
+        assert!(serde_json::from_str::<Priority>("\"P1\"").is_err());
+    }
+}
@@ -0,0 +1,713 @@
+//! Projects, the top level containers for tasks.
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::types::colors::Colors;
+use crate::types::error::{self, BuildError, BuilderError, Resource};
+use crate::types::favorite_unless_deleted;
+use crate::types::items::Item;
+use crate::types::order::Ordered;
+
+/// Longest project name the API accepts, in characters.
+pub const MAX_NAME_LENGTH: usize = 120;
+
+/// JSON keys of the API object that [`Project`] deserializes into named fields,
+/// see [`super::unmodeled_fields`].
+pub(crate) const FIELDS: &[&str] = &[
+    "id",
+    "name",
+    "color",
+    "parent_id",
+    "child_order",
+    "collapsed",
+    "is_deleted",
+    "is_archived",
+    "is_favorite",
+    "inbox_project",
+    "can_assign_tasks",
+    "shared",
+    "sync_id",
+];
+
+/// A Todoist project as returned by the Sync API.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct Project {
+    id: Option<u64>,
+    name: String,
+    color: Colors,
+    parent_id: Option<u64>,
+    child_order: u32,
+    #[serde(deserialize_with = "crate::types::flag")]
+    collapsed: bool,
+    #[serde(deserialize_with = "crate::types::flag")]
+    is_deleted: bool,
+    #[serde(deserialize_with = "crate::types::flag")]
+    is_archived: bool,
+    #[serde(deserialize_with = "crate::types::flag")]
+    is_favorite: bool,
+    /// Only sent by the API for the user's inbox.
+    #[serde(default, deserialize_with = "crate::types::flag")]
+    inbox_project: bool,
+    /// Only sent by the API for shared projects.
+    #[serde(default, deserialize_with = "crate::types::flag")]
+    can_assign_tasks: bool,
+    #[serde(default, deserialize_with = "crate::types::flag")]
+    shared: bool,
+    /// Id of the shared copy of the project, only sent for shared projects.
+    #[serde(default)]
+    sync_id: Option<u64>,
+}
+
+impl Project {
+    pub fn builder() -> ProjectBuilder {
+        ProjectBuilder::default()
+    }
+
+    /// Starts an edit of an existing project. Only projects that came from the
+    /// API, and therefore have an id, can be updated.
+    pub fn to_builder(&self) -> Result<ProjectBuilder, BuildError> {
+        if self.id.is_none() {
+            return Err(BuildError::NotSaved(Resource::Project));
+        }
+
+        Ok(ProjectBuilder {
+            id: self.id,
+            name: Some(self.name.clone()),
+            color: Some(self.color),
+            parent_id: self.parent_id,
+            child_order: Some(self.child_order),
+            collapsed: Some(self.collapsed),
+            is_deleted: Some(self.is_deleted),
+            is_archived: Some(self.is_archived),
+            is_favorite: Some(self.is_favorite),
+            inbox_project: Some(self.inbox_project),
+            can_assign_tasks: Some(self.can_assign_tasks),
+            shared: Some(self.shared),
+            sync_id: self.sync_id,
+        })
+    }
+
+    pub fn id(&self) -> Option<u64> {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn color(&self) -> Colors {
+        self.color
+    }
+
+    pub fn parent_id(&self) -> Option<u64> {
+        self.parent_id
+    }
+
+    pub fn child_order(&self) -> u32 {
+        self.child_order
+    }
+
+    pub fn collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.is_deleted
+    }
+
+    pub fn is_archived(&self) -> bool {
+        self.is_archived
+    }
+
+    pub fn is_favorite(&self) -> bool {
+        self.is_favorite
+    }
+
+    pub fn inbox_project(&self) -> bool {
+        self.inbox_project
+    }
+
+    /// Whether tasks of this shared project can be assigned to collaborators.
+    pub fn can_assign_tasks(&self) -> bool {
+        self.can_assign_tasks
+    }
+
+    /// Whether the project is shared with collaborators.
+    pub fn shared(&self) -> bool {
+        self.shared
+    }
+
+    pub fn sync_id(&self) -> Option<u64> {
+        self.sync_id
+    }
+
+    /// Shifts the project's id and the ids it references by `offset`, see
+    /// [`crate::store::Store::merge`].
+    pub(crate) fn offset_ids(&mut self, offset: u64) {
+        let shift = |id: &mut u64| *id = id.wrapping_add(offset);
+        self.id.iter_mut().for_each(shift);
+        self.parent_id.iter_mut().for_each(shift);
+    }
+}
+
+impl Ordered for Project {
+    fn order(&self) -> u32 {
+        self.child_order
+    }
+
+    fn set_order(&mut self, order: u32) {
+        self.child_order = order;
+    }
+}
+
+/// The project's name.
+impl fmt::Display for Project {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// Number of open tasks in each of `projects`, keyed by project id. Completed
+/// and deleted items are not counted.
+pub fn task_counts(projects: &[Project], items: &[Item]) -> HashMap<u64, usize> {
+    let mut counts: HashMap<u64, usize> = projects
+        .iter()
+        .filter_map(|project| project.id)
+        .map(|id| (id, 0))
+        .collect();
+
+    for item in items {
+        if item.checked() || item.is_deleted() {
+            continue;
+        }
+        if let Some(count) = counts.get_mut(&item.project_id()) {
+            *count += 1;
+        }
+    }
+    counts
+}
+
+/// A project along with its sub-projects.
+#[derive(Debug, PartialEq)]
+pub struct ProjectNode<'a> {
+    pub project: &'a Project,
+    pub children: Vec<ProjectNode<'a>>,
+}
+
+/// Arranges the non-deleted `projects` into a forest following `parent_id`,
+/// siblings sorted by `child_order`. Projects whose parent is missing are
+/// treated as roots.
+pub fn build_tree(projects: &[Project]) -> Vec<ProjectNode<'_>> {
+    let live: Vec<&Project> = projects.iter().filter(|p| !p.is_deleted).collect();
+    let known: Vec<u64> = live.iter().filter_map(|p| p.id).collect();
+
+    fn children_of<'a>(
+        parent: Option<u64>,
+        live: &[&'a Project],
+        known: &[u64],
+    ) -> Vec<ProjectNode<'a>> {
+        let mut children: Vec<&Project> = live
+            .iter()
+            .copied()
+            .filter(|p| match (parent, p.parent_id) {
+                (None, None) => true,
+                (None, Some(id)) => !known.contains(&id),
+                (Some(parent), Some(id)) => parent == id,
+                (Some(_), None) => false,
+            })
+            .collect();
+        children.sort_by_key(|p| p.child_order);
+        children
+            .into_iter()
+            .map(|project| ProjectNode {
+                project,
+                children: match project.id {
+                    Some(id) => children_of(Some(id), live, known),
+                    None => Vec::new(),
+                },
+            })
+            .collect()
+    }
+
+    children_of(None, &live, &known)
+}
+
+/// Renders a project tree as one name per line, indented by two spaces per
+/// level of nesting.
+pub fn render_tree(tree: &[ProjectNode]) -> String {
+    fn render(nodes: &[ProjectNode], depth: usize, out: &mut String) {
+        for node in nodes {
+            out.push_str(&"  ".repeat(depth));
+            out.push_str(&node.project.name);
+            out.push('\n');
+            render(&node.children, depth + 1, out);
+        }
+    }
+
+    let mut out = String::new();
+    render(tree, 0, &mut out);
+    out
+}
+
+/// The names of the project with id `project_id` and of its ancestors, root
+/// first, e.g. `["Work", "Q2", "Launch"]`. The path stops at the first parent
+/// missing from `by_id`, and is empty if the project itself is.
+pub fn breadcrumb(project_id: u64, by_id: &HashMap<u64, Project>) -> Vec<&str> {
+    let mut path = Vec::new();
+    let mut current = by_id.get(&project_id);
+    // Bounded by the number of projects in case the parents form a cycle.
+    while let (Some(project), true) = (current, path.len() < by_id.len()) {
+        path.push(project.name.as_str());
+        current = project.parent_id.and_then(|id| by_id.get(&id));
+    }
+    path.reverse();
+    path
+}
+
+/// Looks projects up by name, e.g. to resolve `#Project` in a query. Deleted
+/// projects are left out.
+///
+/// Like [`crate::types::labels::LabelIndex`], names are matched
+/// case-insensitively.
+#[derive(Debug, Default)]
+pub struct ProjectIndex {
+    ids: HashMap<String, u64>,
+}
+
+impl ProjectIndex {
+    pub fn new<'a, I: IntoIterator<Item = &'a Project>>(projects: I) -> Self {
+        let mut index = ProjectIndex::default();
+        for project in projects {
+            if let (Some(id), false) = (project.id, project.is_deleted) {
+                index.ids.insert(project.name.to_lowercase(), id);
+            }
+        }
+        index
+    }
+
+    pub fn id_of(&self, name: &str) -> Option<u64> {
+        self.ids.get(&name.to_lowercase()).copied()
+    }
+}
+
+/// The fields of a project, see [`ProjectBuilder::is_set`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ProjectField {
+    Id,
+    Name,
+    Color,
+    ParentId,
+    ChildOrder,
+    Collapsed,
+    IsDeleted,
+    IsArchived,
+    IsFavorite,
+    InboxProject,
+    CanAssignTasks,
+    Shared,
+    SyncId,
+}
+
+#[derive(Debug, Default)]
+pub struct ProjectBuilder {
+    id: Option<u64>,
+    name: Option<String>,
+    color: Option<Colors>,
+    parent_id: Option<u64>,
+    child_order: Option<u32>,
+    collapsed: Option<bool>,
+    is_deleted: Option<bool>,
+    is_archived: Option<bool>,
+    is_favorite: Option<bool>,
+    inbox_project: Option<bool>,
+    can_assign_tasks: Option<bool>,
+    shared: Option<bool>,
+    sync_id: Option<u64>,
+}
+
+impl ProjectBuilder {
+    pub fn id(&mut self, value: u64) -> &mut Self {
+        self.id = Some(value);
+        self
+    }
+
+    /// Leading and trailing whitespace is dropped.
+    pub fn name(&mut self, value: &str) -> &mut Self {
+        self.name = Some(value.trim().to_string());
+        self
+    }
+
+    pub fn color(&mut self, value: Colors) -> &mut Self {
+        self.color = Some(value);
+        self
+    }
+
+    pub fn parent_id(&mut self, value: u64) -> &mut Self {
+        self.parent_id = Some(value);
+        self
+    }
+
+    pub fn child_order(&mut self, value: u32) -> &mut Self {
+        self.child_order = Some(value);
+        self
+    }
+
+    pub fn collapsed(&mut self, value: bool) -> &mut Self {
+        self.collapsed = Some(value);
+        self
+    }
+
+    pub fn is_deleted(&mut self, value: bool) -> &mut Self {
+        self.is_deleted = Some(value);
+        self
+    }
+
+    pub fn is_archived(&mut self, value: bool) -> &mut Self {
+        self.is_archived = Some(value);
+        self
+    }
+
+    pub fn is_favorite(&mut self, value: bool) -> &mut Self {
+        self.is_favorite = Some(value);
+        self
+    }
+
+    pub fn inbox_project(&mut self, value: bool) -> &mut Self {
+        self.inbox_project = Some(value);
+        self
+    }
+
+    pub fn can_assign_tasks(&mut self, value: bool) -> &mut Self {
+        self.can_assign_tasks = Some(value);
+        self
+    }
+
+    pub fn shared(&mut self, value: bool) -> &mut Self {
+        self.shared = Some(value);
+        self
+    }
+
+    pub fn sync_id(&mut self, value: u64) -> &mut Self {
+        self.sync_id = Some(value);
+        self
+    }
+
+    /// Arguments of a `project_add` command creating a project with the
+    /// fields set on this builder. Nothing is checked, see
+    /// [`crate::client::CommandBatch::validate`].
+    pub fn add_args(&self) -> Value {
+        let mut args = Map::new();
+        if let Some(name) = &self.name {
+            args.insert(String::from("name"), json!(name));
+        }
+        if let Some(color) = self.color {
+            args.insert(String::from("color"), json!(color));
+        }
+        if let Some(parent_id) = self.parent_id {
+            args.insert(String::from("parent_id"), json!(parent_id));
+        }
+        if let Some(child_order) = self.child_order {
+            args.insert(String::from("child_order"), json!(child_order));
+        }
+        if let Some(is_favorite) = self.is_favorite {
+            args.insert(String::from("is_favorite"), json!(is_favorite));
+        }
+        Value::Object(args)
+    }
+
+    /// Whether `field` was given a value, as opposed to being left to its
+    /// default.
+    pub fn is_set(&self, field: ProjectField) -> bool {
+        match field {
+            ProjectField::Id => self.id.is_some(),
+            ProjectField::Name => self.name.is_some(),
+            ProjectField::Color => self.color.is_some(),
+            ProjectField::ParentId => self.parent_id.is_some(),
+            ProjectField::ChildOrder => self.child_order.is_some(),
+            ProjectField::Collapsed => self.collapsed.is_some(),
+            ProjectField::IsDeleted => self.is_deleted.is_some(),
+            ProjectField::IsArchived => self.is_archived.is_some(),
+            ProjectField::IsFavorite => self.is_favorite.is_some(),
+            ProjectField::InboxProject => self.inbox_project.is_some(),
+            ProjectField::CanAssignTasks => self.can_assign_tasks.is_some(),
+            ProjectField::Shared => self.shared.is_some(),
+            ProjectField::SyncId => self.sync_id.is_some(),
+        }
+    }
+
+    /// Every problem with the fields set so far, in field order.
+    fn validate(&self) -> Vec<BuilderError> {
+        let mut errors = Vec::new();
+        match &self.name {
+            None => errors.push(BuilderError::Missing("name")),
+            Some(name) if name.chars().count() > MAX_NAME_LENGTH => {
+                errors.push(BuilderError::TooLong {
+                    field: "name",
+                    max: MAX_NAME_LENGTH,
+                })
+            }
+            Some(_) => {}
+        }
+        errors
+    }
+
+    /// Like `build`, but reports every problem at once instead of stopping at
+    /// the first one.
+    pub fn build_checked(&self) -> Result<Project, Vec<BuilderError>> {
+        let errors = self.validate();
+        let name = match &self.name {
+            Some(name) if errors.is_empty() => name.clone(),
+            _ => return Err(errors),
+        };
+        let is_deleted = self.is_deleted.unwrap_or(false);
+
+        Ok(Project {
+            id: self.id,
+            name,
+            color: self.color.unwrap_or_default(),
+            parent_id: self.parent_id,
+            child_order: self.child_order.unwrap_or_default(),
+            collapsed: self.collapsed.unwrap_or(false),
+            is_deleted,
+            is_archived: self.is_archived.unwrap_or(false),
+            is_favorite: favorite_unless_deleted(
+                "project",
+                self.is_favorite.unwrap_or(false),
+                is_deleted,
+            ),
+            inbox_project: self.inbox_project.unwrap_or(false),
+            can_assign_tasks: self.can_assign_tasks.unwrap_or(false),
+            shared: self.shared.unwrap_or(false),
+            sync_id: self.sync_id,
+        })
+    }
+
+    pub fn build(&self) -> Result<Project, BuildError> {
+        self.build_checked()
+            .map_err(|errors| error::first(errors, Resource::Project))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_error_test() {
+        assert_eq!(
+            Project::builder().build(),
+            Err(BuildError::MissingName(Resource::Project))
+        );
+        let project = Project::builder().name("Home").build().unwrap();
+        assert!(project.to_builder().is_err());
+    }
+
+    #[test]
+    fn project_create_test() {
+        let project = Project::builder()
+            .name("Home")
+            .color(Colors::Teal)
+            .is_favorite(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(project.name(), "Home");
+        assert_eq!(project.color(), Colors::Teal);
+        assert!(project.is_favorite());
+        assert!(!project.is_deleted());
+    }
+
+    #[test]
+    fn project_name_length_test() {
+        let longest = "é".repeat(MAX_NAME_LENGTH);
+        let project = Project::builder().name(&longest).build().unwrap();
+        assert_eq!(project.name(), longest);
+
+        let too_long = "a".repeat(MAX_NAME_LENGTH + 1);
+        assert_eq!(
+            Project::builder().name(&too_long).build(),
+            Err(BuildError::Invalid(BuilderError::TooLong {
+                field: "name",
+                max: MAX_NAME_LENGTH,
+            }))
+        );
+        assert_eq!(
+            Project::builder().name(&too_long).build_checked(),
+            Err(vec![BuilderError::TooLong {
+                field: "name",
+                max: MAX_NAME_LENGTH,
+            }])
+        );
+    }
+
+    #[test]
+    fn project_name_trim_test() {
+        let project = Project::builder().name("  Foo  ").build().unwrap();
+        assert_eq!(project.name(), "Foo");
+    }
+
+    #[test]
+    fn project_update_test() {
+        let project = Project::builder().id(1).name("Home").build().unwrap();
+        let updated = project.to_builder().unwrap().name("House").build().unwrap();
+
+        assert_eq!(updated.id(), Some(1));
+        assert_eq!(updated.name(), "House");
+    }
+
+    #[test]
+    fn project_can_assign_tasks_test() {
+        let shared: Project = serde_json::from_str(
+            r#"{"id": 1, "name": "Team", "color": 41, "parent_id": null,
+                "child_order": 1, "collapsed": false, "shared": true,
+                "is_deleted": false, "is_archived": false, "is_favorite": false,
+                "can_assign_tasks": true}"#,
+        )
+        .unwrap();
+        assert!(shared.can_assign_tasks());
+        let json = serde_json::to_value(&shared).unwrap();
+        assert_eq!(json["can_assign_tasks"], serde_json::json!(true));
+
+        let personal = Project::builder().name("Home").build().unwrap();
+        assert!(!personal.can_assign_tasks());
+        let team = Project::builder()
+            .name("Team")
+            .can_assign_tasks(true)
+            .build()
+            .unwrap();
+        assert!(team.can_assign_tasks());
+    }
+
+    #[test]
+    fn project_deleted_favorite_test() {
+        let project = Project::builder()
+            .name("Old")
+            .is_favorite(true)
+            .is_deleted(true)
+            .build()
+            .unwrap();
+
+        assert!(project.is_deleted());
+        assert!(!project.is_favorite());
+    }
+
+    #[test]
+    fn task_counts_test() {
+        let projects = vec![
+            Project::builder().id(1).name("Home").build().unwrap(),
+            Project::builder().id(2).name("Work").build().unwrap(),
+            Project::builder().id(3).name("Empty").build().unwrap(),
+        ];
+        let mut builder = Item::builder();
+        builder.user_id(1).content("Task");
+        let items = vec![
+            builder.project_id(1).build().unwrap(),
+            builder.project_id(2).build().unwrap(),
+            builder.project_id(2).build().unwrap(),
+            builder.project_id(2).checked(true).build().unwrap(),
+        ];
+
+        let counts = task_counts(&projects, &items);
+        assert_eq!(counts[&1], 1);
+        assert_eq!(counts[&2], 2);
+        assert_eq!(counts[&3], 0);
+    }
+
+    #[test]
+    fn render_tree_test() {
+        let projects = vec![
+            Project::builder()
+                .id(1)
+                .name("Work")
+                .child_order(2)
+                .build()
+                .unwrap(),
+            Project::builder()
+                .id(2)
+                .name("Home")
+                .child_order(1)
+                .build()
+                .unwrap(),
+            Project::builder()
+                .id(3)
+                .name("Launch")
+                .parent_id(1)
+                .build()
+                .unwrap(),
+            Project::builder()
+                .id(4)
+                .name("Garden")
+                .parent_id(2)
+                .is_deleted(true)
+                .build()
+                .unwrap(),
+        ];
+
+        let tree = build_tree(&projects);
+        assert_eq!(render_tree(&tree), "Home\nWork\n  Launch\n");
+    }
+
+    #[test]
+    fn project_is_set_test() {
+        let mut builder = Project::builder();
+        builder.name("Home");
+
+        assert!(builder.is_set(ProjectField::Name));
+        assert!(!builder.is_set(ProjectField::Color));
+        assert_eq!(builder.build().unwrap().color(), Colors::default());
+    }
+
+    #[test]
+    fn breadcrumb_test() {
+        let mut by_id = HashMap::new();
+        let mut add = |builder: &mut ProjectBuilder| {
+            let project = builder.build().unwrap();
+            by_id.insert(project.id().unwrap(), project);
+        };
+        add(Project::builder().id(1).name("Work"));
+        add(Project::builder().id(2).name("Q2").parent_id(1));
+        add(Project::builder().id(3).name("Launch").parent_id(2));
+        add(Project::builder().id(4).name("Orphan").parent_id(9));
+
+        assert_eq!(breadcrumb(3, &by_id), vec!["Work", "Q2", "Launch"]);
+        assert_eq!(breadcrumb(1, &by_id), vec!["Work"]);
+        assert_eq!(breadcrumb(4, &by_id), vec!["Orphan"]);
+        assert!(breadcrumb(9, &by_id).is_empty());
+    }
+
+    #[test]
+    fn project_shared_test() {
+        let shared = Project::builder()
+            .id(1)
+            .name("Team")
+            .shared(true)
+            .sync_id(42)
+            .can_assign_tasks(true)
+            .build()
+            .unwrap();
+        assert!(shared.shared());
+        assert_eq!(shared.sync_id(), Some(42));
+
+        let json = serde_json::to_value(&shared).unwrap();
+        assert_eq!(json["shared"], serde_json::json!(true));
+        assert_eq!(json["sync_id"], serde_json::json!(42));
+        assert_eq!(serde_json::from_value::<Project>(json).unwrap(), shared);
+
+        let builder = shared.to_builder().unwrap();
+        assert!(builder.is_set(ProjectField::Shared));
+        assert!(builder.is_set(ProjectField::SyncId));
+
+        let personal = Project::builder().name("Home").build().unwrap();
+        assert!(!personal.shared());
+        assert_eq!(personal.sync_id(), None);
+    }
+
+    #[test]
+    fn project_display_test() {
+        let project = Project::builder().name("Home").build().unwrap();
+        assert_eq!(format!("{}", project), "Home");
+    }
+}
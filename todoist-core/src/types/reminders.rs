@@ -0,0 +1,241 @@
+//! Reminders sent ahead of or at a given time for a task.
+use serde::{Deserialize, Serialize};
+
+use crate::types::dates::DueDate;
+use crate::types::error::{self, BuildError, BuilderError, Resource};
+
+/// JSON keys of the API object that [`Reminder`] deserializes into named
+/// fields, see [`super::unmodeled_fields`].
+pub(crate) const FIELDS: &[&str] = &[
+    "id",
+    "item_id",
+    "type",
+    "due",
+    "minute_offset",
+    "is_deleted",
+];
+
+/// How the time of a reminder is given.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ReminderType {
+    /// A number of minutes before the task's due time, see `minute_offset`.
+    #[default]
+    Relative,
+    /// A fixed time, see `due`.
+    Absolute,
+    /// Entering or leaving a place. Only read, this crate cannot build them.
+    Location,
+}
+
+/// A Todoist reminder as returned by the Sync API.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Reminder {
+    id: Option<u64>,
+    item_id: u64,
+    #[serde(rename = "type")]
+    kind: ReminderType,
+    #[serde(default)]
+    due: Option<DueDate>,
+    #[serde(default)]
+    minute_offset: Option<u32>,
+    #[serde(deserialize_with = "crate::types::flag")]
+    is_deleted: bool,
+}
+
+impl Reminder {
+    pub fn builder() -> ReminderBuilder {
+        ReminderBuilder::default()
+    }
+
+    pub fn id(&self) -> Option<u64> {
+        self.id
+    }
+
+    pub fn item_id(&self) -> u64 {
+        self.item_id
+    }
+
+    pub fn kind(&self) -> ReminderType {
+        self.kind
+    }
+
+    /// When an absolute reminder fires.
+    pub fn due(&self) -> Option<&DueDate> {
+        self.due.as_ref()
+    }
+
+    /// How many minutes before the task is due a relative reminder fires.
+    pub fn minute_offset(&self) -> Option<u32> {
+        self.minute_offset
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.is_deleted
+    }
+}
+
+/// The fields of a reminder, see [`ReminderBuilder::is_set`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ReminderField {
+    Id,
+    ItemId,
+    Kind,
+    Due,
+    MinuteOffset,
+    IsDeleted,
+}
+
+#[derive(Debug, Default)]
+pub struct ReminderBuilder {
+    id: Option<u64>,
+    item_id: Option<u64>,
+    kind: Option<ReminderType>,
+    due: Option<DueDate>,
+    minute_offset: Option<u32>,
+    is_deleted: Option<bool>,
+}
+
+impl ReminderBuilder {
+    /// A reminder `minutes_before` the task with id `item_id` is due.
+    pub fn relative(item_id: u64, minutes_before: u32) -> ReminderBuilder {
+        ReminderBuilder {
+            item_id: Some(item_id),
+            kind: Some(ReminderType::Relative),
+            minute_offset: Some(minutes_before),
+            ..Default::default()
+        }
+    }
+
+    /// A reminder at `due` for the task with id `item_id`.
+    pub fn absolute(item_id: u64, due: DueDate) -> ReminderBuilder {
+        ReminderBuilder {
+            item_id: Some(item_id),
+            kind: Some(ReminderType::Absolute),
+            due: Some(due),
+            ..Default::default()
+        }
+    }
+
+    pub fn id(&mut self, value: u64) -> &mut Self {
+        self.id = Some(value);
+        self
+    }
+
+    pub fn item_id(&mut self, value: u64) -> &mut Self {
+        self.item_id = Some(value);
+        self
+    }
+
+    pub fn due(&mut self, value: DueDate) -> &mut Self {
+        self.due = Some(value);
+        self
+    }
+
+    pub fn minute_offset(&mut self, value: u32) -> &mut Self {
+        self.minute_offset = Some(value);
+        self
+    }
+
+    pub fn is_deleted(&mut self, value: bool) -> &mut Self {
+        self.is_deleted = Some(value);
+        self
+    }
+
+    /// Whether `field` was given a value, as opposed to being left to its
+    /// default.
+    pub fn is_set(&self, field: ReminderField) -> bool {
+        match field {
+            ReminderField::Id => self.id.is_some(),
+            ReminderField::ItemId => self.item_id.is_some(),
+            ReminderField::Kind => self.kind.is_some(),
+            ReminderField::Due => self.due.is_some(),
+            ReminderField::MinuteOffset => self.minute_offset.is_some(),
+            ReminderField::IsDeleted => self.is_deleted.is_some(),
+        }
+    }
+
+    /// Every problem with the fields set so far, in field order.
+    fn validate(&self) -> Vec<BuilderError> {
+        let mut errors = Vec::new();
+        if self.item_id.is_none() {
+            errors.push(BuilderError::Missing("item_id"));
+        }
+        match self.kind.unwrap_or_default() {
+            ReminderType::Relative if self.minute_offset.is_none() => {
+                errors.push(BuilderError::Missing("minute_offset"))
+            }
+            ReminderType::Absolute if self.due.is_none() => {
+                errors.push(BuilderError::Missing("due"))
+            }
+            _ => {}
+        }
+        errors
+    }
+
+    /// Like `build`, but reports every problem at once instead of stopping at
+    /// the first one.
+    pub fn build_checked(&self) -> Result<Reminder, Vec<BuilderError>> {
+        let errors = self.validate();
+        let item_id = match self.item_id {
+            Some(item_id) if errors.is_empty() => item_id,
+            _ => return Err(errors),
+        };
+
+        Ok(Reminder {
+            id: self.id,
+            item_id,
+            kind: self.kind.unwrap_or_default(),
+            due: self.due.clone(),
+            minute_offset: self.minute_offset,
+            is_deleted: self.is_deleted.unwrap_or(false),
+        })
+    }
+
+    pub fn build(&self) -> Result<Reminder, BuildError> {
+        self.build_checked()
+            .map_err(|errors| error::first(errors, Resource::Reminder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reminder_error_test() {
+        assert_eq!(Reminder::builder().build(), Err(BuildError::MissingItemId));
+        assert_eq!(
+            Reminder::builder().item_id(1).build(),
+            Err(BuildError::MissingMinuteOffset)
+        );
+        assert_eq!(
+            Reminder::builder().item_id(1).build_checked(),
+            Err(vec![BuilderError::Missing("minute_offset")])
+        );
+    }
+
+    #[test]
+    fn reminder_relative_test() {
+        let reminder = ReminderBuilder::relative(7, 30).build().unwrap();
+
+        assert_eq!(reminder.kind(), ReminderType::Relative);
+        assert_eq!(reminder.minute_offset(), Some(30));
+        let json = serde_json::to_value(&reminder).unwrap();
+        assert_eq!(json["type"], json!("relative"));
+        assert_eq!(json["item_id"], json!(7));
+    }
+
+    #[test]
+    fn reminder_absolute_test() {
+        let due = DueDate::builder().date("2021-04-12").build().unwrap();
+        let reminder = ReminderBuilder::absolute(7, due.clone()).build().unwrap();
+
+        assert_eq!(reminder.kind(), ReminderType::Absolute);
+        assert_eq!(reminder.due(), Some(&due));
+        assert_eq!(reminder.minute_offset(), None);
+        let json = serde_json::to_value(&reminder).unwrap();
+        assert_eq!(json["type"], json!("absolute"));
+    }
+}
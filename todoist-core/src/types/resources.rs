@@ -0,0 +1,219 @@
+//! The projects, sections and items of an account together, to walk them as
+//! the trees the apps show.
+use std::collections::HashMap;
+
+use crate::types::items::Item;
+use crate::types::projects::Project;
+use crate::types::sections::Section;
+
+/// Projects, sections and items, e.g. as returned by a full sync.
+///
+/// The lookups return resources sorted the way the apps list them. Deleted
+/// resources are left out, except by the `_including_deleted` variants.
+#[derive(Debug, Default)]
+pub struct Resources {
+    projects: Vec<Project>,
+    sections: Vec<Section>,
+    items: Vec<Item>,
+}
+
+impl Resources {
+    pub fn new(projects: Vec<Project>, sections: Vec<Section>, items: Vec<Item>) -> Self {
+        Resources {
+            projects,
+            sections,
+            items,
+        }
+    }
+
+    pub fn projects(&self) -> &[Project] {
+        &self.projects
+    }
+
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// The top-level items of the project with id `project_id`: those without
+    /// a section first, then those of each section by `section_order`, each
+    /// group by `child_order`. Subtasks are found with [`Self::children_of`].
+    pub fn items_in_project(&self, project_id: u64) -> Vec<&Item> {
+        self.project_items(project_id, false)
+    }
+
+    pub fn items_in_project_including_deleted(&self, project_id: u64) -> Vec<&Item> {
+        self.project_items(project_id, true)
+    }
+
+    /// The direct subprojects of the project with id `parent_id`, by
+    /// `child_order`.
+    pub fn subprojects(&self, parent_id: u64) -> Vec<&Project> {
+        self.project_children(parent_id, false)
+    }
+
+    pub fn subprojects_including_deleted(&self, parent_id: u64) -> Vec<&Project> {
+        self.project_children(parent_id, true)
+    }
+
+    /// The direct subtasks of the item with id `item_id`, by `child_order`.
+    pub fn children_of(&self, item_id: u64) -> Vec<&Item> {
+        self.item_children(item_id, false)
+    }
+
+    pub fn children_of_including_deleted(&self, item_id: u64) -> Vec<&Item> {
+        self.item_children(item_id, true)
+    }
+
+    fn project_items(&self, project_id: u64, include_deleted: bool) -> Vec<&Item> {
+        let section_orders: HashMap<u64, u32> = self
+            .sections
+            .iter()
+            .filter_map(|section| Some((section.id()?, section.section_order())))
+            .collect();
+        let mut items: Vec<&Item> = self
+            .items
+            .iter()
+            .filter(|item| item.project_id() == project_id && item.parent_id().is_none())
+            .filter(|item| include_deleted || !item.is_deleted())
+            .collect();
+        // Items of a section missing from `sections` go last.
+        items.sort_by_key(|item| {
+            let section = item
+                .section_id()
+                .map(|id| section_orders.get(&id).copied().unwrap_or(u32::MAX));
+            (section, item.child_order())
+        });
+        items
+    }
+
+    fn project_children(&self, parent_id: u64, include_deleted: bool) -> Vec<&Project> {
+        let mut projects: Vec<&Project> = self
+            .projects
+            .iter()
+            .filter(|project| project.parent_id() == Some(parent_id))
+            .filter(|project| include_deleted || !project.is_deleted())
+            .collect();
+        projects.sort_by_key(|project| project.child_order());
+        projects
+    }
+
+    fn item_children(&self, item_id: u64, include_deleted: bool) -> Vec<&Item> {
+        let mut items: Vec<&Item> = self
+            .items
+            .iter()
+            .filter(|item| item.parent_id() == Some(item_id))
+            .filter(|item| include_deleted || !item.is_deleted())
+            .collect();
+        items.sort_by_key(|item| item.child_order());
+        items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resources() -> Resources {
+        let project = |id: u64, parent: Option<u64>, order: u32, deleted: bool| {
+            let mut builder = Project::builder();
+            builder
+                .id(id)
+                .name("Project")
+                .child_order(order)
+                .is_deleted(deleted);
+            if let Some(parent) = parent {
+                builder.parent_id(parent);
+            }
+            builder.build().unwrap()
+        };
+        let section = |id: u64, order: u32| {
+            Section::builder()
+                .id(id)
+                .name("Section")
+                .project_id(1)
+                .section_order(order)
+                .build()
+                .unwrap()
+        };
+        let item = |id: u64, section: Option<u64>, parent: Option<u64>, order: u32, deleted| {
+            Item::builder()
+                .id(id)
+                .user_id(1)
+                .project_id(1)
+                .content("Task")
+                .section_id_opt(section)
+                .parent_id_opt(parent)
+                .child_order(order)
+                .is_deleted(deleted)
+                .build()
+                .unwrap()
+        };
+
+        Resources::new(
+            vec![
+                project(1, None, 1, false),
+                project(2, Some(1), 2, false),
+                project(3, Some(1), 1, false),
+                project(4, Some(1), 3, true),
+            ],
+            vec![section(10, 2), section(11, 1)],
+            vec![
+                item(100, Some(10), None, 1, false),
+                item(101, Some(11), None, 2, false),
+                item(102, Some(11), None, 1, false),
+                item(103, None, None, 5, false),
+                item(104, None, None, 6, true),
+                item(105, None, Some(103), 2, false),
+                item(106, None, Some(103), 1, false),
+                item(107, None, Some(103), 3, true),
+            ],
+        )
+    }
+
+    fn ids<'a, I: IntoIterator<Item = &'a Item>>(items: I) -> Vec<u64> {
+        items.into_iter().filter_map(Item::id).collect()
+    }
+
+    #[test]
+    fn resources_items_in_project_test() {
+        let resources = resources();
+
+        assert_eq!(ids(resources.items_in_project(1)), vec![103, 102, 101, 100]);
+        assert_eq!(
+            ids(resources.items_in_project_including_deleted(1)),
+            vec![103, 104, 102, 101, 100]
+        );
+        assert!(resources.items_in_project(2).is_empty());
+    }
+
+    #[test]
+    fn resources_subprojects_test() {
+        let resources = resources();
+        let project_ids = |projects: Vec<&Project>| -> Vec<u64> {
+            projects.into_iter().filter_map(Project::id).collect()
+        };
+
+        assert_eq!(project_ids(resources.subprojects(1)), vec![3, 2]);
+        assert_eq!(
+            project_ids(resources.subprojects_including_deleted(1)),
+            vec![3, 2, 4]
+        );
+        assert!(resources.subprojects(3).is_empty());
+    }
+
+    #[test]
+    fn resources_children_of_test() {
+        let resources = resources();
+
+        assert_eq!(ids(resources.children_of(103)), vec![106, 105]);
+        assert_eq!(
+            ids(resources.children_of_including_deleted(103)),
+            vec![106, 105, 107]
+        );
+        assert!(resources.children_of(100).is_empty());
+    }
+}
@@ -0,0 +1,389 @@
+//! Sections, which split the tasks of a project into groups.
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::dates;
+use crate::types::error::{self, BuildError, BuilderError, Resource};
+use crate::types::order::Ordered;
+use crate::types::projects::Project;
+
+/// Longest section name the API accepts, in characters.
+pub const MAX_NAME_LENGTH: usize = 120;
+
+/// JSON keys of the API object that [`Section`] deserializes into named fields,
+/// see [`super::unmodeled_fields`].
+pub(crate) const FIELDS: &[&str] = &[
+    "id",
+    "name",
+    "project_id",
+    "section_order",
+    "collapsed",
+    "is_deleted",
+    "is_archived",
+    "date_archived",
+];
+
+/// A Todoist section as returned by the Sync API.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct Section {
+    id: Option<u64>,
+    name: String,
+    project_id: u64,
+    section_order: u32,
+    #[serde(deserialize_with = "crate::types::flag")]
+    collapsed: bool,
+    #[serde(deserialize_with = "crate::types::flag")]
+    is_deleted: bool,
+    #[serde(deserialize_with = "crate::types::flag")]
+    is_archived: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date_archived: Option<String>,
+}
+
+impl Section {
+    pub fn builder() -> SectionBuilder {
+        SectionBuilder::default()
+    }
+
+    /// Starts an edit of an existing section. Only sections that came from the
+    /// API, and therefore have an id, can be updated.
+    pub fn to_builder(&self) -> Result<SectionBuilder, BuildError> {
+        if self.id.is_none() {
+            return Err(BuildError::NotSaved(Resource::Section));
+        }
+
+        Ok(SectionBuilder {
+            id: self.id,
+            name: Some(self.name.clone()),
+            project_id: Some(self.project_id),
+            section_order: Some(self.section_order),
+            collapsed: Some(self.collapsed),
+            is_deleted: Some(self.is_deleted),
+            is_archived: Some(self.is_archived),
+            date_archived: self.date_archived.clone(),
+        })
+    }
+
+    pub fn id(&self) -> Option<u64> {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn project_id(&self) -> u64 {
+        self.project_id
+    }
+
+    pub fn section_order(&self) -> u32 {
+        self.section_order
+    }
+
+    pub fn collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.is_deleted
+    }
+
+    pub fn is_archived(&self) -> bool {
+        self.is_archived
+    }
+
+    /// When the section was archived, as a `YYYY-MM-DD` date or an RFC 3339
+    /// date and time.
+    pub fn date_archived(&self) -> Option<&str> {
+        self.date_archived.as_deref()
+    }
+}
+
+impl Ordered for Section {
+    fn order(&self) -> u32 {
+        self.section_order
+    }
+
+    fn set_order(&mut self, order: u32) {
+        self.section_order = order;
+    }
+}
+
+/// The section's name.
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// The sections whose project is not in `projects`, e.g. because it was
+/// deleted and the sections were not synced since.
+pub fn orphans<'a>(sections: &'a [Section], projects: &[Project]) -> Vec<&'a Section> {
+    sections
+        .iter()
+        .filter(|section| {
+            !projects
+                .iter()
+                .any(|project| project.id() == Some(section.project_id))
+        })
+        .collect()
+}
+
+/// The fields of a section, see [`SectionBuilder::is_set`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SectionField {
+    Id,
+    Name,
+    ProjectId,
+    SectionOrder,
+    Collapsed,
+    IsDeleted,
+    IsArchived,
+    DateArchived,
+}
+
+#[derive(Debug, Default)]
+pub struct SectionBuilder {
+    id: Option<u64>,
+    name: Option<String>,
+    project_id: Option<u64>,
+    section_order: Option<u32>,
+    collapsed: Option<bool>,
+    is_deleted: Option<bool>,
+    is_archived: Option<bool>,
+    date_archived: Option<String>,
+}
+
+impl SectionBuilder {
+    pub fn id(&mut self, value: u64) -> &mut Self {
+        self.id = Some(value);
+        self
+    }
+
+    /// Leading and trailing whitespace is dropped.
+    pub fn name(&mut self, value: &str) -> &mut Self {
+        self.name = Some(value.trim().to_string());
+        self
+    }
+
+    pub fn project_id(&mut self, value: u64) -> &mut Self {
+        self.project_id = Some(value);
+        self
+    }
+
+    pub fn section_order(&mut self, value: u32) -> &mut Self {
+        self.section_order = Some(value);
+        self
+    }
+
+    pub fn collapsed(&mut self, value: bool) -> &mut Self {
+        self.collapsed = Some(value);
+        self
+    }
+
+    pub fn is_deleted(&mut self, value: bool) -> &mut Self {
+        self.is_deleted = Some(value);
+        self
+    }
+
+    pub fn is_archived(&mut self, value: bool) -> &mut Self {
+        self.is_archived = Some(value);
+        self
+    }
+
+    /// Sets when the section was archived, as a `YYYY-MM-DD` date or an
+    /// RFC 3339 date and time.
+    pub fn date_archived(&mut self, value: &str) -> &mut Self {
+        self.date_archived = Some(value.to_string());
+        self
+    }
+
+    /// Whether `field` was given a value, as opposed to being left to its
+    /// default.
+    pub fn is_set(&self, field: SectionField) -> bool {
+        match field {
+            SectionField::Id => self.id.is_some(),
+            SectionField::Name => self.name.is_some(),
+            SectionField::ProjectId => self.project_id.is_some(),
+            SectionField::SectionOrder => self.section_order.is_some(),
+            SectionField::Collapsed => self.collapsed.is_some(),
+            SectionField::IsDeleted => self.is_deleted.is_some(),
+            SectionField::IsArchived => self.is_archived.is_some(),
+            SectionField::DateArchived => self.date_archived.is_some(),
+        }
+    }
+
+    /// Every problem with the fields set so far, in field order.
+    fn validate(&self) -> Vec<BuilderError> {
+        let mut errors = Vec::new();
+        match &self.name {
+            None => errors.push(BuilderError::Missing("name")),
+            Some(name) if name.chars().count() > MAX_NAME_LENGTH => {
+                errors.push(BuilderError::TooLong {
+                    field: "name",
+                    max: MAX_NAME_LENGTH,
+                })
+            }
+            Some(_) => {}
+        }
+        if self.project_id.is_none() {
+            errors.push(BuilderError::Missing("project_id"));
+        }
+        if let Err(error) = dates::check_timestamp("date_archived", self.date_archived.as_deref()) {
+            errors.push(error);
+        }
+        errors
+    }
+
+    /// Like `build`, but reports every problem at once instead of stopping at
+    /// the first one.
+    pub fn build_checked(&self) -> Result<Section, Vec<BuilderError>> {
+        let errors = self.validate();
+        let (name, project_id) = match (&self.name, self.project_id) {
+            (Some(name), Some(project_id)) if errors.is_empty() => (name.clone(), project_id),
+            _ => return Err(errors),
+        };
+
+        Ok(Section {
+            id: self.id,
+            name,
+            project_id,
+            section_order: self.section_order.unwrap_or_default(),
+            collapsed: self.collapsed.unwrap_or(false),
+            is_deleted: self.is_deleted.unwrap_or(false),
+            is_archived: self.is_archived.unwrap_or(false),
+            date_archived: self.date_archived.clone(),
+        })
+    }
+
+    pub fn build(&self) -> Result<Section, BuildError> {
+        self.build_checked()
+            .map_err(|errors| error::first(errors, Resource::Section))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn section_error_test() {
+        assert_eq!(
+            Section::builder().build(),
+            Err(BuildError::MissingName(Resource::Section))
+        );
+        assert_eq!(
+            Section::builder().name("Backlog").build(),
+            Err(BuildError::MissingProjectId(Resource::Section))
+        );
+        let section = Section::builder()
+            .name("Backlog")
+            .project_id(1)
+            .build()
+            .unwrap();
+        assert!(section.to_builder().is_err());
+    }
+
+    #[test]
+    fn section_name_length_test() {
+        let longest = "é".repeat(MAX_NAME_LENGTH);
+        let section = Section::builder()
+            .name(&longest)
+            .project_id(1)
+            .build()
+            .unwrap();
+        assert_eq!(section.name(), longest);
+
+        let mut builder = Section::builder();
+        builder.name(&"a".repeat(MAX_NAME_LENGTH + 1));
+        assert_eq!(
+            builder.project_id(1).build(),
+            Err(BuildError::Invalid(BuilderError::TooLong {
+                field: "name",
+                max: MAX_NAME_LENGTH,
+            }))
+        );
+        assert_eq!(
+            builder.build_checked(),
+            Err(vec![BuilderError::TooLong {
+                field: "name",
+                max: MAX_NAME_LENGTH,
+            }])
+        );
+    }
+
+    #[test]
+    fn section_create_test() {
+        let section = Section::builder()
+            .name(" Backlog ")
+            .project_id(1)
+            .section_order(2)
+            .build()
+            .unwrap();
+
+        assert_eq!(section.name(), "Backlog");
+        assert_eq!(section.project_id(), 1);
+        assert_eq!(section.section_order(), 2);
+        assert!(!section.collapsed());
+    }
+
+    #[test]
+    fn section_update_test() {
+        let section = Section::builder()
+            .id(5)
+            .name("Backlog")
+            .project_id(1)
+            .build()
+            .unwrap();
+        let updated = section
+            .to_builder()
+            .unwrap()
+            .collapsed(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(updated.id(), Some(5));
+        assert!(updated.collapsed());
+    }
+
+    #[test]
+    fn orphans_test() {
+        let projects = vec![Project::builder().id(1).name("Home").build().unwrap()];
+        let mut builder = Section::builder();
+        builder.name("Backlog");
+        let sections = vec![
+            builder.id(10).project_id(1).build().unwrap(),
+            builder.id(20).project_id(2).build().unwrap(),
+        ];
+
+        let orphans = orphans(&sections, &projects);
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].id(), Some(20));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn section_date_archived_test() {
+        let mut builder = Section::builder();
+        builder.name("Groceries").project_id(2);
+
+        builder.date_archived("2021-03-01T10:00:00+01:00");
+        let section = builder.build().unwrap();
+        assert_eq!(section.date_archived(), Some("2021-03-01T10:00:00+01:00"));
+
+        builder.date_archived("2021-13-01");
+        assert_eq!(
+            builder.build(),
+            Err(BuildError::Invalid(BuilderError::InvalidDate(
+                "date_archived"
+            )))
+        );
+
+        builder.date_archived("not a date");
+        assert_eq!(
+            builder.build_checked(),
+            Err(vec![BuilderError::InvalidDate("date_archived")])
+        );
+    }
+}
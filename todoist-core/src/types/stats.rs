@@ -0,0 +1,116 @@
+//! Productivity stats: karma and how many tasks were completed.
+use serde::{Deserialize, Serialize};
+
+/// How many tasks of a project were completed in a [`PeriodStats`].
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct ProjectCompleted {
+    /// Id of the project.
+    id: u64,
+    completed: u64,
+}
+
+impl ProjectCompleted {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn completed(&self) -> u64 {
+        self.completed
+    }
+}
+
+/// Completed tasks over a day or a week.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct PeriodStats {
+    date: String,
+    total_completed: u64,
+    #[serde(default)]
+    items: Vec<ProjectCompleted>,
+}
+
+impl PeriodStats {
+    /// The day, as `YYYY-MM-DD`, or the week, as `YYYY-MM-DD/YYYY-MM-DD` from
+    /// its first to its last day.
+    pub fn date(&self) -> &str {
+        &self.date
+    }
+
+    pub fn total_completed(&self) -> u64 {
+        self.total_completed
+    }
+
+    /// The completed tasks per project, leaving out projects with none.
+    pub fn items(&self) -> &[ProjectCompleted] {
+        &self.items
+    }
+}
+
+/// The user's productivity stats, as returned by the `completed/get_stats`
+/// endpoint.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Stats {
+    karma: f64,
+    completed_count: u64,
+    #[serde(default)]
+    days_items: Vec<PeriodStats>,
+    #[serde(default)]
+    week_items: Vec<PeriodStats>,
+}
+
+impl Stats {
+    pub fn karma(&self) -> f64 {
+        self.karma
+    }
+
+    /// Tasks completed since the account was created.
+    pub fn completed_count(&self) -> u64 {
+        self.completed_count
+    }
+
+    /// Completed tasks of the last days, most recent first.
+    pub fn days_items(&self) -> &[PeriodStats] {
+        &self.days_items
+    }
+
+    /// Completed tasks of the last weeks, most recent first.
+    pub fn week_items(&self) -> &[PeriodStats] {
+        &self.week_items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_deserialize_test() {
+        let stats: Stats = serde_json::from_str(
+            r#"{
+                "karma_last_update": 50.0,
+                "karma_trend": "up",
+                "days_items": [
+                    {"date": "2021-03-02", "total_completed": 3,
+                     "items": [{"id": 2, "completed": 2}, {"id": 5, "completed": 1}]},
+                    {"date": "2021-03-01", "total_completed": 0, "items": []}
+                ],
+                "completed_count": 1234,
+                "karma": 6840.0,
+                "week_items": [
+                    {"date": "2021-03-01/2021-03-07", "total_completed": 3,
+                     "items": [{"id": 2, "completed": 3}]}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(stats.karma(), 6840.0);
+        assert_eq!(stats.completed_count(), 1234);
+        assert_eq!(stats.days_items().len(), 2);
+        let today = &stats.days_items()[0];
+        assert_eq!(today.date(), "2021-03-02");
+        assert_eq!(today.total_completed(), 3);
+        assert_eq!(today.items()[1].id(), 5);
+        assert_eq!(today.items()[1].completed(), 1);
+        assert_eq!(stats.week_items()[0].date(), "2021-03-01/2021-03-07");
+    }
+}
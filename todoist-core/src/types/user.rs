@@ -0,0 +1,196 @@
+//! The user owning the synced resources.
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// JSON keys of the API object that [`User`] deserializes into named fields,
+/// see [`super::unmodeled_fields`].
+pub(crate) const FIELDS: &[&str] = &[
+    "id",
+    "email",
+    "full_name",
+    "tz_info",
+    "start_day",
+    "next_week",
+    "time_format",
+    "date_format",
+    "lang",
+    "is_premium",
+    "inbox_project",
+];
+
+/// The timezone of a user, and its offset from UTC at the time of the sync.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct TzInfo {
+    timezone: String,
+    gmt_string: String,
+    hours: i8,
+    minutes: u8,
+    #[serde(deserialize_with = "crate::types::flag")]
+    is_dst: bool,
+}
+
+impl TzInfo {
+    /// Name of the timezone, e.g. `"Europe/Paris"`.
+    pub fn timezone(&self) -> &str {
+        &self.timezone
+    }
+
+    /// The offset from UTC, e.g. `"+01:00"`.
+    pub fn gmt_string(&self) -> &str {
+        &self.gmt_string
+    }
+
+    pub fn hours(&self) -> i8 {
+        self.hours
+    }
+
+    pub fn minutes(&self) -> u8 {
+        self.minutes
+    }
+
+    /// Whether daylight saving time is in effect.
+    pub fn is_dst(&self) -> bool {
+        self.is_dst
+    }
+}
+
+/// A Todoist user as returned by the Sync API. Users are only ever read, so
+/// there is no builder; settings are changed with [`UserSetting`].
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct User {
+    id: u64,
+    email: String,
+    full_name: String,
+    tz_info: TzInfo,
+    start_day: u8,
+    next_week: u8,
+    time_format: u8,
+    date_format: u8,
+    lang: String,
+    #[serde(deserialize_with = "crate::types::flag")]
+    is_premium: bool,
+    inbox_project: u64,
+}
+
+impl User {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+
+    pub fn full_name(&self) -> &str {
+        &self.full_name
+    }
+
+    pub fn tz_info(&self) -> &TzInfo {
+        &self.tz_info
+    }
+
+    /// Name of the user's timezone, e.g. `"Europe/Paris"`, the one to give
+    /// timed due dates of new items.
+    pub fn timezone(&self) -> &str {
+        self.tz_info.timezone()
+    }
+
+    /// First day of the week, from 1 for Monday to 7 for Sunday.
+    pub fn start_day(&self) -> u8 {
+        self.start_day
+    }
+
+    /// Day tasks are postponed to with "next week", from 1 for Monday to 7
+    /// for Sunday.
+    pub fn next_week(&self) -> u8 {
+        self.next_week
+    }
+
+    /// 0 for 24 hour times, 1 for 12 hour times.
+    pub fn time_format(&self) -> u8 {
+        self.time_format
+    }
+
+    /// 0 for `DD-MM-YYYY` dates, 1 for `MM-DD-YYYY` dates.
+    pub fn date_format(&self) -> u8 {
+        self.date_format
+    }
+
+    /// Language of the apps, e.g. `"en"`.
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    pub fn is_premium(&self) -> bool {
+        self.is_premium
+    }
+
+    /// Id of the user's Inbox project.
+    pub fn inbox_project(&self) -> u64 {
+        self.inbox_project
+    }
+}
+
+/// A user setting that can be changed with a `user_update` command. The values
+/// use the same encoding as the matching getters of [`User`].
+#[derive(Debug, PartialEq, Clone)]
+pub enum UserSetting {
+    StartDay(u8),
+    NextWeek(u8),
+    TimeFormat(u8),
+    DateFormat(u8),
+    Timezone(String),
+}
+
+impl UserSetting {
+    /// Arguments of the `user_update` command changing this setting.
+    pub(crate) fn args(&self) -> Value {
+        match self {
+            UserSetting::StartDay(day) => json!({ "start_day": day }),
+            UserSetting::NextWeek(day) => json!({ "next_week": day }),
+            UserSetting::TimeFormat(format) => json!({ "time_format": format }),
+            UserSetting::DateFormat(format) => json!({ "date_format": format }),
+            UserSetting::Timezone(timezone) => json!({ "timezone": timezone }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_deserialize_test() {
+        let user: User = serde_json::from_value(json!({
+            "id": 1,
+            "email": "bob@example.com",
+            "full_name": "Bob",
+            "tz_info": {
+                "timezone": "America/New_York",
+                "gmt_string": "-04:00",
+                "hours": -4,
+                "minutes": 0,
+                "is_dst": 1
+            },
+            "start_day": 1,
+            "next_week": 1,
+            "time_format": 1,
+            "date_format": 1,
+            "lang": "en",
+            "is_premium": false,
+            "inbox_project": 2,
+            "karma": 1000.0
+        }))
+        .unwrap();
+
+        assert_eq!(user.id(), 1);
+        assert_eq!(user.email(), "bob@example.com");
+        assert_eq!(user.timezone(), "America/New_York");
+        assert_eq!(user.tz_info().hours(), -4);
+        assert!(user.tz_info().is_dst());
+        assert_eq!(user.time_format(), 1);
+        assert_eq!(user.lang(), "en");
+        assert!(!user.is_premium());
+        assert_eq!(user.inbox_project(), 2);
+    }
+}
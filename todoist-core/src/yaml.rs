@@ -0,0 +1,40 @@
+//! YAML helpers for human-editable dumps of the resource types.
+//!
+//! Every type in [`crate::types`] derives serde's traits, so any other serde
+//! format works too; these are only shortcuts for the common case.
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+pub fn to_yaml<T: Serialize>(value: &T) -> Result<String, serde_yaml::Error> {
+    serde_yaml::to_string(value)
+}
+
+pub fn from_yaml<T: DeserializeOwned>(yaml: &str) -> Result<T, serde_yaml::Error> {
+    serde_yaml::from_str(yaml)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::items::Item;
+
+    #[test]
+    fn item_yaml_round_trip_test() {
+        let item: Item = serde_json::from_str(
+            r#"{
+                "id": 1, "user_id": 2, "project_id": 3,
+                "content": "Buy milk", "description": "Two litres",
+                "due": {"date": "2021-03-01", "timezone": null, "string": "Mar 1",
+                        "lang": "en", "is_recurring": false},
+                "priority": 4, "parent_id": null, "child_order": 1,
+                "section_id": null, "labels": [4, 5],
+                "checked": false, "is_deleted": false
+            }"#,
+        )
+        .unwrap();
+
+        let yaml = to_yaml(&item).unwrap();
+        let parsed: Item = from_yaml(&yaml).unwrap();
+        assert_eq!(parsed, item);
+    }
+}